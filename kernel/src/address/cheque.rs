@@ -0,0 +1,138 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+use property::Property;
+
+use super::{
+    error::{Error, Result},
+    Address, AddressBuilder, AddressFormat, AddressKind, Network, BLAKE160_SIZE,
+};
+use crate::{script::SystemScript, utilities};
+
+/// The size of the cheque lock args.
+pub const CHEQUE_ARGS_SIZE: usize = BLAKE160_SIZE * 2;
+
+/// Cheque Args
+///
+/// The args of the cheque lock: the first 20 bytes of the receiver lock hash, then the first
+/// 20 bytes of the sender lock hash.
+#[derive(Property, Clone, PartialEq, Eq, Hash)]
+#[property(get(public), set(disable), mut(disable))]
+pub struct ChequeArgs {
+    receiver_lock_hash: [u8; BLAKE160_SIZE],
+    sender_lock_hash: [u8; BLAKE160_SIZE],
+}
+
+fn prefix(lock_hash: &[u8; 32]) -> [u8; BLAKE160_SIZE] {
+    let mut data = [0u8; BLAKE160_SIZE];
+    data.copy_from_slice(&lock_hash[..BLAKE160_SIZE]);
+    data
+}
+
+impl fmt::Debug for ChequeArgs {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ChequeArgs {{")?;
+        write!(
+            f,
+            " receiver_lock_hash: {}",
+            utilities::hex_string(&self.receiver_lock_hash[..])
+        )?;
+        write!(
+            f,
+            " , sender_lock_hash: {}",
+            utilities::hex_string(&self.sender_lock_hash[..])
+        )?;
+        write!(f, " }}")
+    }
+}
+
+impl ChequeArgs {
+    /// Creates the args from the full lock hashes of the receiver and the sender.
+    pub fn new(receiver_lock_hash: &[u8; 32], sender_lock_hash: &[u8; 32]) -> Self {
+        Self {
+            receiver_lock_hash: prefix(receiver_lock_hash),
+            sender_lock_hash: prefix(sender_lock_hash),
+        }
+    }
+
+    /// Creates the args from the addresses of the receiver and the sender.
+    pub fn from_addresses(receiver: &Address, sender: &Address) -> Self {
+        Self::new(&receiver.to_script().hash(), &sender.to_script().hash())
+    }
+
+    pub fn from_slice(data: &[u8]) -> Result<Self> {
+        if data.len() != CHEQUE_ARGS_SIZE {
+            return Err(Error::ChequeArgs);
+        }
+        let mut receiver_lock_hash = [0u8; BLAKE160_SIZE];
+        let mut sender_lock_hash = [0u8; BLAKE160_SIZE];
+        receiver_lock_hash.copy_from_slice(&data[..BLAKE160_SIZE]);
+        sender_lock_hash.copy_from_slice(&data[BLAKE160_SIZE..]);
+        Ok(Self {
+            receiver_lock_hash,
+            sender_lock_hash,
+        })
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(CHEQUE_ARGS_SIZE);
+        buf.extend_from_slice(&self.receiver_lock_hash[..]);
+        buf.extend_from_slice(&self.sender_lock_hash[..]);
+        buf
+    }
+
+    /// Creates the cheque address in the full format on the network.
+    pub fn address(&self, network: Network) -> Result<Address> {
+        let script = network
+            .spec()
+            .known_script_of(SystemScript::Cheque)
+            .ok_or(Error::UnavailableKnownScript(SystemScript::Cheque, network))?;
+        AddressBuilder::default()
+            .network(network)
+            .format(AddressFormat::Full)
+            .code_hash(script.to_code_hash())
+            .args_simple(self.serialize())
+            .build()
+    }
+
+    /// Checks if the address is the receiver of the cheque.
+    pub fn is_receiver(&self, address: &Address) -> bool {
+        prefix(&address.to_script().hash()) == self.receiver_lock_hash
+    }
+
+    /// Checks if the address is the sender of the cheque.
+    pub fn is_sender(&self, address: &Address) -> bool {
+        prefix(&address.to_script().hash()) == self.sender_lock_hash
+    }
+}
+
+impl Address {
+    /// Creates a cheque address in the full format from the receiver and the sender.
+    ///
+    /// The receiver and the sender should be on the same network.
+    pub fn cheque(receiver: &Self, sender: &Self) -> Result<Self> {
+        if receiver.network() != sender.network() {
+            return Err(Error::MismatchedNetwork(
+                *receiver.network(),
+                *sender.network(),
+            ));
+        }
+        ChequeArgs::from_addresses(receiver, sender).address(*receiver.network())
+    }
+
+    /// Returns the args of a cheque address.
+    pub fn cheque_args(&self) -> Option<ChequeArgs> {
+        if self.kind() == AddressKind::Cheque {
+            ChequeArgs::from_slice(self.to_script().args()).ok()
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,227 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::{collections::HashMap, str::FromStr};
+
+use property::Property;
+
+use super::{error::Error, Address, ADDRESS_MAX_LENGTH};
+
+/// The max count of the substitution errors which are corrected.
+pub const MAX_CORRECTIONS: usize = 2;
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const SEPARATOR: char = '1';
+const CHECKSUM_SIZE: usize = 6;
+const GENERATORS: [u32; 5] = [
+    0x3b6a_57b2,
+    0x2650_8e6d,
+    0x1ea1_19fa,
+    0x3d42_33dd,
+    0x2a14_62b3,
+];
+const BECH32_CONST: u32 = 1;
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+/// Address Diagnosis
+///
+/// Why an address string is invalid, and the candidate addresses which are corrected from it.
+/// The candidates are only suggestions, they should be confirmed by the user.
+#[derive(Property, Debug)]
+#[property(get(public), set(disable), mut(disable))]
+pub struct AddressDiagnosis {
+    /// The error when decode the address, `None` if the address is valid.
+    error: Option<Error>,
+    /// The positions of the characters which are likely wrong.
+    positions: Vec<usize>,
+    candidates: Vec<AddressCandidate>,
+}
+
+/// A candidate address which is corrected from an invalid address string.
+#[derive(Property, Debug)]
+#[property(get(public), set(disable), mut(disable))]
+pub struct AddressCandidate {
+    address: Address,
+    /// The positions of the corrected characters.
+    positions: Vec<usize>,
+}
+
+fn polymod_step(chk: u32, value: u8) -> u32 {
+    let b = chk >> 25;
+    let mut chk = ((chk & 0x01ff_ffff) << 5) ^ u32::from(value);
+    for (i, generator) in GENERATORS.iter().enumerate() {
+        if (b >> i) & 1 == 1 {
+            chk ^= generator;
+        }
+    }
+    chk
+}
+
+fn residue(hrp: &str, data: &[u8]) -> u32 {
+    let hrp = hrp.as_bytes();
+    let mut chk = 1;
+    for c in hrp {
+        chk = polymod_step(chk, c >> 5);
+    }
+    chk = polymod_step(chk, 0);
+    for c in hrp {
+        chk = polymod_step(chk, c & 0x1f);
+    }
+    for v in data {
+        chk = polymod_step(chk, *v);
+    }
+    chk
+}
+
+/// Finds the substitutions which make the checksum valid.
+///
+/// The checksum is linear, so the effect of an error only depends on its position and its value.
+/// The candidates with the fewest wrong characters are returned, each substitution is a pair of
+/// the position in the data and the xor difference.
+fn find_substitutions(hrp: &str, data: &[u8], known: &[usize]) -> Vec<Vec<(usize, u8)>> {
+    let len = data.len();
+    // The effect of the value `v` at the position `len - 1 - m` is `step^m(v)`.
+    let mut effects = vec![[0u32; 32]; len];
+    for v in 1..32u8 {
+        let mut effect = u32::from(v);
+        for m in 0..len {
+            effects[len - 1 - m][usize::from(v)] = effect;
+            effect = polymod_step(effect, 0);
+        }
+    }
+    let mut lookup = HashMap::new();
+    for (i, effects) in effects.iter().enumerate() {
+        for (v, effect) in effects.iter().enumerate().skip(1) {
+            lookup.insert(*effect, (i, v as u8));
+        }
+    }
+    let residue = residue(hrp, data);
+    let mut results = Vec::new();
+    for target in &[BECH32_CONST, BECH32M_CONST] {
+        let diff = residue ^ target;
+        if diff == 0 {
+            if !known.is_empty() {
+                results.push(Vec::new());
+            }
+            continue;
+        }
+        if let Some(&(i, v)) = lookup.get(&diff) {
+            results.push(vec![(i, v)]);
+        }
+        for (i, effects) in effects.iter().enumerate() {
+            for (v, effect) in effects.iter().enumerate().skip(1) {
+                if let Some(&(j, w)) = lookup.get(&(diff ^ effect)) {
+                    if i < j {
+                        results.push(vec![(i, v as u8), (j, w)]);
+                    }
+                }
+            }
+        }
+    }
+    // The count of the wrong characters, the invalid characters are always wrong.
+    let cost = |substitutions: &Vec<(usize, u8)>| {
+        known.len()
+            + substitutions
+                .iter()
+                .filter(|(i, _)| !known.contains(i))
+                .count()
+    };
+    results.retain(|substitutions| cost(substitutions) <= MAX_CORRECTIONS);
+    if let Some(min) = results.iter().map(cost).min() {
+        results.retain(|substitutions| cost(substitutions) == min);
+    }
+    results
+}
+
+impl AddressDiagnosis {
+    /// Checks if the address is valid.
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+impl Address {
+    /// Diagnoses an address string, locates the wrong characters and suggests corrections.
+    ///
+    /// At most `MAX_CORRECTIONS` substitution errors in the data part are corrected,
+    /// the human-readable part should be correct.
+    pub fn diagnose(input: &str) -> AddressDiagnosis {
+        let error = match Self::from_str(input) {
+            Ok(_) => {
+                return AddressDiagnosis {
+                    error: None,
+                    positions: Vec::new(),
+                    candidates: Vec::new(),
+                }
+            }
+            Err(error) => error,
+        };
+        let mut diagnosis = AddressDiagnosis {
+            error: Some(error),
+            positions: Vec::new(),
+            candidates: Vec::new(),
+        };
+        // Too long to be corrected, only the length error is reported.
+        if input.len() > ADDRESS_MAX_LENGTH {
+            return diagnosis;
+        }
+        let lowercase = input.to_lowercase();
+        let separator = match lowercase.rfind(SEPARATOR) {
+            Some(separator) if lowercase.is_ascii() => separator,
+            _ => return diagnosis,
+        };
+        let (hrp, rest) = lowercase.split_at(separator);
+        let start = separator + 1;
+        let mut known = Vec::new();
+        let data = rest[1..]
+            .bytes()
+            .enumerate()
+            .map(|(i, c)| {
+                CHARSET.iter().position(|x| *x == c).unwrap_or_else(|| {
+                    known.push(i);
+                    0
+                }) as u8
+            })
+            .collect::<Vec<_>>();
+        diagnosis.positions = known.iter().map(|i| start + i).collect();
+        if data.len() < CHECKSUM_SIZE || known.len() > MAX_CORRECTIONS {
+            return diagnosis;
+        }
+        let mut located = known.clone();
+        for substitutions in find_substitutions(hrp, &data, &known) {
+            let mut corrected = data.clone();
+            let mut positions = known.clone();
+            for (i, v) in substitutions {
+                corrected[i] ^= v;
+                if !positions.contains(&i) {
+                    positions.push(i);
+                }
+            }
+            positions.sort_unstable();
+            let candidate = corrected
+                .iter()
+                .map(|v| char::from(CHARSET[usize::from(*v)]))
+                .collect::<String>();
+            let candidate = format!("{}{}{}", hrp, SEPARATOR, candidate);
+            if let Ok(address) = Self::from_str(&candidate) {
+                for i in &positions {
+                    if !located.contains(i) {
+                        located.push(*i);
+                    }
+                }
+                diagnosis.candidates.push(AddressCandidate {
+                    address,
+                    positions: positions.iter().map(|i| start + i).collect(),
+                });
+            }
+        }
+        located.sort_unstable();
+        diagnosis.positions = located.iter().map(|i| start + i).collect();
+        diagnosis
+    }
+}
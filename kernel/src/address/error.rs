@@ -8,6 +8,9 @@
 
 use thiserror::Error;
 
+use super::{AddressFormat, CodeHashIndex, Network, PayloadFormat};
+use crate::{script::SystemScript, secp256k1};
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("internal error: should be unreachable, {0}")]
@@ -15,22 +18,71 @@ pub enum Error {
 
     #[error("bech32 error: {0}")]
     Bech32(bech32::Error),
+    #[error("secp256k1 error: {0}")]
+    Secp256k1(#[from] secp256k1::Error),
 
+    #[error("invalid address length: {0}")]
+    InvalidLength(usize),
     #[error("unknown network: {0}")]
     UnknownNetwork(String),
+    #[error("invalid network: {0}")]
+    InvalidNetwork(String),
+    #[error("duplicate network: {0}")]
+    DuplicateNetwork(String),
     #[error("unknown payload format: {0}")]
     UnknownPayloadFormat(u8),
     #[error("unknown code hash index: {0}")]
     UnknownCodeHashIndex(u8),
+    #[error("code hash index {0:?} is unavailable on {1}")]
+    UnavailableCodeHashIndex(CodeHashIndex, Network),
+    #[error("script {0} is unavailable on {1}")]
+    UnavailableKnownScript(SystemScript, Network),
+    #[error("mismatched networks: {0} and {1}")]
+    MismatchedNetwork(Network, Network),
+    #[error("unknown code hash type: {0}")]
+    UnknownCodeHashType(u8),
+    #[error("mismatched bech32 variant for payload format {0:?}")]
+    MismatchedVariant(PayloadFormat),
     #[error("invalid data since offset {0}")]
     InvalidDataSince(usize),
 
+    #[error("unsupported address format {0:?} for the code hash")]
+    UnsupportedFormat(AddressFormat),
+    #[error("no short format for the code hash")]
+    NoShortFormat,
+    #[error("no public key hash in the address")]
+    NoPubKeyHash,
+
     #[error("args error: Secp256k1Blake160")]
     Secp256k1Blake160Args,
     #[error("args error: ShortFormatArgs")]
     ShortFormatArgs,
     #[error("args error: MultiSig")]
     MultiSigArgs,
+    #[error("invalid multisig config")]
+    InvalidMultiSigConfig,
+    #[error("unsupported multisig version: {0}")]
+    UnsupportedMultiSigVersion(u8),
+    #[error("too many multisig keys: {0}")]
+    TooManyMultiSigKeys(usize),
+    #[error("multisig threshold should not be zero")]
+    ZeroMultiSigThreshold,
+    #[error("multisig threshold {0} is larger than the keys count {1}")]
+    MultiSigThresholdTooLarge(u8, usize),
+    #[error("multisig first n required {0} is larger than the threshold {1}")]
+    MultiSigFirstNTooLarge(u8, u8),
+    #[error("duplicate multisig key: {0}")]
+    DuplicateMultiSigKey(String),
+    #[error("invalid since: {0}")]
+    InvalidSince(String),
+    #[error("args error: AnyoneCanPay")]
+    AnyoneCanPayArgs,
+    #[error("args error: Cheque")]
+    ChequeArgs,
+    #[error("unknown omnilock auth flag: {0}")]
+    UnknownOmniAuthFlag(u8),
+    #[error("args error: Omnilock")]
+    OmniLockArgs,
 }
 
 pub type Result<T> = ::std::result::Result<T, Error>;
@@ -6,7 +6,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use std::{fmt, str};
+use std::{cmp, convert, fmt, hash, str};
 
 use bech32::{FromBase32, ToBase32};
 use property::Property;
@@ -14,7 +14,27 @@ use property::Property;
 pub mod error;
 use error::{Error, Result};
 
-use crate::{blake2b, utilities};
+mod cheque;
+mod diagnosis;
+mod multisig;
+mod network;
+mod omnilock;
+mod since;
+mod timelock;
+pub use cheque::{ChequeArgs, CHEQUE_ARGS_SIZE};
+pub use diagnosis::{AddressCandidate, AddressDiagnosis, MAX_CORRECTIONS};
+pub use multisig::{MultiSigConfig, MULTISIG_KEYS_MAX, MULTISIG_VERSION};
+pub use network::{CustomNetwork, Network, NetworkSpec};
+pub use omnilock::{OmniAuth, OmniAuthFlag, OmniLockArgs, OMNI_AUTH_SIZE};
+pub use since::{Since, SinceMetric};
+pub use timelock::TimeLock;
+
+use crate::{
+    blake2b,
+    script::{KnownScript, SystemScript},
+    secp256k1::Signature,
+    utilities, PubKeyHash, Script,
+};
 
 #[cfg(test)]
 mod tests;
@@ -22,37 +42,53 @@ mod tests;
 pub const CODE_HASH_SIZE: usize = 32;
 pub const BLAKE160_SIZE: usize = 20;
 pub const SINCE_SIZE: usize = 8;
+pub const ACP_ARGS_MAX_SIZE: usize = BLAKE160_SIZE + 2;
+/// The max length of an address.
+///
+/// The 90 characters limit of BIP-173 is not applied to CKB addresses, since the full format
+/// addresses with long args exceed it. But the checksum could not detect errors reliably when
+/// the length exceeds the length of the BCH code, which is 1023.
+pub const ADDRESS_MAX_LENGTH: usize = 1023;
 
-/// CKB Network
+/// Payload Format Types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Network {
-    Main,
-    Test,
+pub enum PayloadFormat {
+    Full = 0x00,
+    Short = 0x01,
+    FullData = 0x02,
+    FullType = 0x04,
 }
 
-/// Payload Format Types
+/// Address Format
+///
+/// The format which is used to encode an address.
+/// The full format (CKB2021) is recommended, the others are deprecated.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum PayloadFormat {
+pub enum AddressFormat {
     Short,
-    Full(CodeHashType),
+    Full,
+    DeprecatedFull,
 }
 
 /// Code Hash Index
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CodeHashIndex {
     Secp256k1Blake160 = 0x00,
     Secp256k1MultiSig = 0x01,
+    AnyoneCanPay = 0x02,
 }
 
 /// Code Hash Type
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum CodeHashType {
-    Data = 0x02,
-    Type = 0x04,
+    Data = 0x00,
+    Type = 0x01,
+    Data1 = 0x02,
+    Data2 = 0x04,
 }
 
 /// Code Hash
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CodeHash {
     Index(CodeHashIndex),
     Data {
@@ -61,8 +97,21 @@ pub enum CodeHash {
     },
 }
 
+/// Address Kind
+///
+/// The kind of the lock script of an address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AddressKind {
+    SingleSig,
+    MultiSig,
+    AnyoneCanPay,
+    Cheque,
+    Omnilock,
+    Unknown,
+}
+
 /// Args
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Args {
     Simple(Vec<u8>),
     MultiSig {
@@ -70,14 +119,30 @@ pub enum Args {
         first_n_required: u8,
         threshold: u8,
         contents: Vec<[u8; BLAKE160_SIZE]>,
-        since: Option<[u8; 8]>,
+        since: Option<Since>,
+    },
+    /// Args for the anyone-can-pay lock.
+    ///
+    /// The minimum amounts are exponents: the minimum transfer amount is `10^min_ckb` shannons
+    /// (or `10^min_udt` for UDT), and `min_udt` requires `min_ckb`.
+    AnyoneCanPay {
+        pubkey_hash: [u8; BLAKE160_SIZE],
+        min_ckb: Option<u8>,
+        min_udt: Option<u8>,
     },
+    Omnilock(OmniLockArgs),
 }
 
+/// Address
+///
+/// Addresses are compared by the networks and the lock scripts, not the encodings.
+/// For example, an address in the short format equals to the same lock in the full format.
 #[derive(Property, Clone)]
 #[property(get(public), set(disable), mut(disable))]
 pub struct Address {
     network: Network,
+    #[property(get(type = "copy"))]
+    format: AddressFormat,
     code_hash: CodeHash,
     args: Args,
 }
@@ -86,13 +151,14 @@ pub struct Address {
 #[property(get(disable), set(public, prefix = "", type = "own"), mut(disable))]
 pub struct AddressBuilder {
     network: Network,
+    format: Option<AddressFormat>,
     code_hash: CodeHash,
     args: Args,
 }
 
-impl Default for Network {
+impl Default for AddressFormat {
     fn default() -> Self {
-        Network::Main
+        AddressFormat::Full
     }
 }
 
@@ -114,49 +180,40 @@ impl Default for Args {
     }
 }
 
-impl Network {
-    pub fn value(self) -> &'static str {
-        match self {
-            Self::Main => "ckb",
-            Self::Test => "ckt",
-        }
+impl PayloadFormat {
+    pub fn value(self) -> u8 {
+        self as u8
     }
 
-    pub fn from_value(value: &str) -> Result<Self> {
+    pub fn from_value(value: u8) -> Result<Self> {
         match value {
-            "ckb" => Ok(Self::Main),
-            "ckt" => Ok(Self::Test),
-            v => Err(Error::UnknownNetwork(v.to_owned())),
+            0x00 => Ok(Self::Full),
+            0x01 => Ok(Self::Short),
+            0x02 => Ok(Self::FullData),
+            0x04 => Ok(Self::FullType),
+            v => Err(Error::UnknownPayloadFormat(v)),
         }
     }
-}
 
-impl fmt::Display for Network {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let s = match self {
-            Self::Main => "mainnet",
-            Self::Test => "testnet",
-        };
-        write!(f, "{}", s)
+    pub fn address_format(self) -> AddressFormat {
+        match self {
+            Self::Full => AddressFormat::Full,
+            Self::Short => AddressFormat::Short,
+            Self::FullData | Self::FullType => AddressFormat::DeprecatedFull,
+        }
     }
-}
 
-impl PayloadFormat {
-    pub fn value(self) -> u8 {
-        if let Self::Full(t) = self {
-            t.value()
-        } else {
-            0x01 // Short
+    pub fn variant(self) -> bech32::Variant {
+        match self {
+            Self::Full => bech32::Variant::Bech32m,
+            Self::Short | Self::FullData | Self::FullType => bech32::Variant::Bech32,
         }
     }
+}
 
-    pub fn from_value(value: u8) -> Result<Self> {
-        match value {
-            0x01 => Ok(Self::Short),
-            0x02 => Ok(Self::Full(CodeHashType::Data)),
-            0x04 => Ok(Self::Full(CodeHashType::Type)),
-            v => Err(Error::UnknownPayloadFormat(v)),
-        }
+impl AddressFormat {
+    pub fn is_deprecated(self) -> bool {
+        self != Self::Full
     }
 }
 
@@ -169,15 +226,65 @@ impl CodeHashIndex {
         match value {
             0x00 => Ok(Self::Secp256k1Blake160),
             0x01 => Ok(Self::Secp256k1MultiSig),
+            0x02 => Ok(Self::AnyoneCanPay),
             v => Err(Error::UnknownCodeHashIndex(v)),
         }
     }
+
+    /// Resolves the index to the well-known code hash on the network.
+    pub fn resolve(self, network: Network) -> Option<(CodeHashType, [u8; CODE_HASH_SIZE])> {
+        network.spec().code_hash(self)
+    }
 }
 
 impl CodeHashType {
     pub fn value(self) -> u8 {
         self as u8
     }
+
+    pub fn from_value(value: u8) -> Result<Self> {
+        match value {
+            0x00 => Ok(Self::Data),
+            0x01 => Ok(Self::Type),
+            0x02 => Ok(Self::Data1),
+            0x04 => Ok(Self::Data2),
+            v => Err(Error::UnknownCodeHashType(v)),
+        }
+    }
+
+    /// The payload format of the deprecated full format for this hash type.
+    ///
+    /// Only `Data` and `Type` are supported by the deprecated full format.
+    pub fn deprecated_payload_format(self) -> Option<PayloadFormat> {
+        match self {
+            Self::Data => Some(PayloadFormat::FullData),
+            Self::Type => Some(PayloadFormat::FullType),
+            Self::Data1 | Self::Data2 => None,
+        }
+    }
+}
+
+impl From<CodeHashIndex> for AddressKind {
+    fn from(index: CodeHashIndex) -> Self {
+        match index {
+            CodeHashIndex::Secp256k1Blake160 => Self::SingleSig,
+            CodeHashIndex::Secp256k1MultiSig => Self::MultiSig,
+            CodeHashIndex::AnyoneCanPay => Self::AnyoneCanPay,
+        }
+    }
+}
+
+impl CodeHash {
+    /// Returns the deployed system script of the code hash on the network.
+    pub fn known_script(&self, network: Network) -> Option<&'static KnownScript> {
+        let (hash_type, content) = match *self {
+            CodeHash::Index(index) => index.resolve(network)?,
+            CodeHash::Data { hash_type, content } => (hash_type, content),
+        };
+        network
+            .spec()
+            .known_script_by_code_hash(hash_type, &content[..])
+    }
 }
 
 impl fmt::Display for CodeHash {
@@ -210,23 +317,69 @@ impl Args {
                 contents,
                 since,
             } => {
-                let len = 4 + BLAKE160_SIZE * contents.len();
-                let mut bin = Vec::with_capacity(len);
-                bin.push(*version);
-                bin.push(*first_n_required);
-                bin.push(*threshold);
-                bin.push(contents.len() as u8);
-                for content in &contents[..] {
-                    bin.extend_from_slice(&content[..]);
-                }
+                let mut bin = Vec::new();
+                multisig::serialize_into(
+                    &mut bin,
+                    *version,
+                    *first_n_required,
+                    *threshold,
+                    contents,
+                );
                 let hash = blake2b::blake160(&bin);
                 buf.extend_from_slice(&hash[..]);
                 if let Some(since) = since {
-                    buf.extend_from_slice(&since[..]);
+                    buf.extend_from_slice(&since.to_le_bytes()[..]);
+                }
+            }
+            Args::AnyoneCanPay {
+                pubkey_hash,
+                min_ckb,
+                min_udt,
+            } => {
+                buf.extend_from_slice(&pubkey_hash[..]);
+                if let Some(min_ckb) = min_ckb {
+                    buf.push(*min_ckb);
                 }
+                if let Some(min_udt) = min_udt {
+                    buf.push(*min_udt);
+                }
+            }
+            Args::Omnilock(ref args) => {
+                args.serialize_into(buf);
             }
         }
     }
+
+    /// Returns the multisig config if the args are multisig args.
+    pub fn multisig_config(&self) -> Option<MultiSigConfig> {
+        if let Self::MultiSig {
+            version,
+            first_n_required,
+            threshold,
+            ref contents,
+            ..
+        } = *self
+        {
+            MultiSigConfig::new(version, first_n_required, threshold, contents.clone()).ok()
+        } else {
+            None
+        }
+    }
+
+    pub fn anyone_can_pay_from_slice(data: &[u8]) -> Result<Self> {
+        if data.len() < BLAKE160_SIZE || data.len() > ACP_ARGS_MAX_SIZE {
+            return Err(Error::AnyoneCanPayArgs);
+        }
+        let mut pubkey_hash = [0u8; BLAKE160_SIZE];
+        pubkey_hash.copy_from_slice(&data[..BLAKE160_SIZE]);
+        let min_ckb = data.get(BLAKE160_SIZE).cloned();
+        let min_udt = data.get(BLAKE160_SIZE + 1).cloned();
+        Ok(Self::AnyoneCanPay {
+            pubkey_hash,
+            min_ckb,
+            min_udt,
+        })
+    }
 }
 
 impl fmt::Display for Args {
@@ -260,44 +413,68 @@ impl fmt::Display for Args {
                 }
                 write!(f, "]")?;
                 if let Some(since) = since {
-                    write!(f, " since: {}", utilities::hex_string(&since[..]))?;
+                    write!(f, " since: {}", since)?;
+                }
+                write!(f, " }}")?;
+            }
+            Self::AnyoneCanPay {
+                pubkey_hash,
+                min_ckb,
+                min_udt,
+            } => {
+                write!(f, " AnyoneCanPay {{")?;
+                write!(
+                    f,
+                    " pubkey_hash: {}",
+                    utilities::hex_string(&pubkey_hash[..])
+                )?;
+                if let Some(min_ckb) = min_ckb {
+                    write!(f, " min_ckb: {}", min_ckb)?;
+                }
+                if let Some(min_udt) = min_udt {
+                    write!(f, " min_udt: {}", min_udt)?;
                 }
                 write!(f, " }}")?;
             }
+            Self::Omnilock(args) => {
+                write!(f, " Omnilock({:?})", args)?;
+            }
         };
         write!(f, " }}")
     }
 }
 
 impl Address {
-    pub fn into_builder(self) -> AddressBuilder {
-        let Self {
-            network,
-            code_hash,
-            args,
-        } = self;
-        AddressBuilder {
-            network,
-            code_hash,
-            args,
-        }
+    /// Creates an address in the full format from a lock script.
+    ///
+    /// The address is checked by the builder, the same as an address parsed from a string.
+    pub fn from_script(script: Script, network: Network) -> Result<Self> {
+        let (content, hash_type, args) = script.into_parts();
+        AddressBuilder::default()
+            .network(network)
+            .format(AddressFormat::Full)
+            .code_hash(CodeHash::Data { hash_type, content })
+            .args(Args::Simple(args))
+            .build()
     }
-}
 
-impl fmt::Debug for Address {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Address {{")?;
-        write!(f, " network: {}", self.network)?;
-        write!(f, " , code_hash: {}", self.code_hash)?;
-        write!(f, " , args: {}", self.args)?;
-        write!(f, " }}")
+    /// Returns the lock script of the address.
+    ///
+    /// The code hash index of a short format address is resolved on its network.
+    pub fn to_script(&self) -> Script {
+        let (hash_type, code_hash) = match self.code_hash {
+            CodeHash::Index(index) => index
+                .resolve(self.network)
+                .unwrap_or_else(|| unreachable!("code hash index is checked when build")),
+            CodeHash::Data { hash_type, content } => (hash_type, content),
+        };
+        let mut args = Vec::new();
+        self.args.serialize_into(&mut args);
+        Script::new(code_hash, hash_type, args)
     }
-}
 
-impl fmt::Display for Address {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let hrp = self.network.value();
-        let data: Vec<u8> = match self.code_hash {
+    fn payload(&self) -> Result<(Vec<u8>, bech32::Variant)> {
+        match self.code_hash {
             CodeHash::Index(index) => {
                 let mut data = Vec::with_capacity(2 + BLAKE160_SIZE);
                 data.push(PayloadFormat::Short.value());
@@ -328,48 +505,272 @@ impl fmt::Display for Address {
                                 ))
                             }
                         }
+                        _ => Err(Error::Unreachable(
+                            "unsupported args for Secp256k1MultiSig".to_owned(),
+                        )),
+                    },
+                    CodeHashIndex::AnyoneCanPay => match self.args {
+                        Args::Simple(_) | Args::AnyoneCanPay { .. } => {
+                            self.args.serialize_into(&mut data);
+                            Ok(data)
+                        }
+                        _ => Err(Error::Unreachable(
+                            "unsupported args for AnyoneCanPay".to_owned(),
+                        )),
                     },
                 }
+                .map(|data| (data, PayloadFormat::Short.variant()))
             }
             CodeHash::Data {
                 hash_type,
                 ref content,
             } => {
-                let args_len = match self.args {
-                    Args::Simple(ref args) => args.len(),
-                    Args::MultiSig { since, .. } => {
-                        BLAKE160_SIZE + since.map(|x| x.len()).unwrap_or(0)
-                    }
+                let mut args = Vec::new();
+                self.args.serialize_into(&mut args);
+                let mut data = Vec::with_capacity(2 + CODE_HASH_SIZE + args.len());
+                let payload_format = if self.format == AddressFormat::DeprecatedFull {
+                    let payload_format = hash_type
+                        .deprecated_payload_format()
+                        .ok_or(Error::UnsupportedFormat(self.format))?;
+                    data.push(payload_format.value());
+                    data.extend_from_slice(&content[..]);
+                    payload_format
+                } else {
+                    data.push(PayloadFormat::Full.value());
+                    data.extend_from_slice(&content[..]);
+                    data.push(hash_type.value());
+                    PayloadFormat::Full
                 };
-                let mut data = Vec::with_capacity(1 + CODE_HASH_SIZE + content.len() + args_len);
-                data.push(PayloadFormat::Full(hash_type).value());
-                data.extend_from_slice(&content[..]);
-                self.args.serialize_into(&mut data);
-                Ok(data)
+                data.extend_from_slice(&args[..]);
+                Ok((data, payload_format.variant()))
             }
         }
-        .unwrap();
-        bech32::encode_to_fmt(f, hrp, data.to_base32()).unwrap()
+    }
+
+    /// Checks if the two addresses point to the same lock script, regardless of their networks.
+    pub fn same_lock(&self, other: &Self) -> bool {
+        self.to_script() == other.to_script()
+    }
+
+    /// Returns the deployed system script of the lock script.
+    pub fn known_script(&self) -> Option<&'static KnownScript> {
+        self.code_hash.known_script(self.network)
+    }
+
+    /// Returns the kind of the lock script.
+    ///
+    /// The code hashes for the short format indexes are checked first, then the known scripts.
+    pub fn kind(&self) -> AddressKind {
+        let index = match self.code_hash {
+            CodeHash::Index(index) => Some(index),
+            CodeHash::Data {
+                hash_type,
+                ref content,
+            } => self.network.spec().code_hash_index(hash_type, &content[..]),
+        };
+        if let Some(index) = index {
+            return index.into();
+        }
+        match self.known_script().map(KnownScript::system_script) {
+            Some(SystemScript::Secp256k1Blake160) => AddressKind::SingleSig,
+            Some(SystemScript::Secp256k1MultiSig) => AddressKind::MultiSig,
+            Some(SystemScript::AnyoneCanPay) => AddressKind::AnyoneCanPay,
+            Some(SystemScript::Cheque) => AddressKind::Cheque,
+            Some(SystemScript::Omnilock) => AddressKind::Omnilock,
+            _ => AddressKind::Unknown,
+        }
+    }
+
+    /// Returns the public key hash of a single-sig or an anyone-can-pay address.
+    pub fn pubkey_hash(&self) -> Option<PubKeyHash> {
+        let args = self.to_script().into_parts().2;
+        let valid = match self.kind() {
+            AddressKind::SingleSig => args.len() == BLAKE160_SIZE,
+            AddressKind::AnyoneCanPay => {
+                args.len() >= BLAKE160_SIZE && args.len() <= ACP_ARGS_MAX_SIZE
+            }
+            _ => false,
+        };
+        if valid {
+            let mut data = [0u8; BLAKE160_SIZE];
+            data.copy_from_slice(&args[..BLAKE160_SIZE]);
+            Some(PubKeyHash::from_secp256k1_blake160(data))
+        } else {
+            None
+        }
+    }
+
+    /// Checks if the signature of the message was signed by the owner of a single-sig or an
+    /// anyone-can-pay address, the same as the lock script does.
+    pub fn verify_signature(&self, input: &[u8], sig: &Signature) -> Result<()> {
+        self.pubkey_hash()
+            .ok_or(Error::NoPubKeyHash)?
+            .verify(input, sig)
+            .map_err(Into::into)
+    }
+
+    /// Returns the multisig script hash of a multisig address.
+    pub fn multisig_hash(&self) -> Option<[u8; BLAKE160_SIZE]> {
+        self.multisig_args().map(|(hash, _)| hash)
+    }
+
+    /// Returns the since of a multisig address, if it has a valid one.
+    pub fn since(&self) -> Option<Since> {
+        self.multisig_args().and_then(|(_, since)| since)
+    }
+
+    // The hash is returned even if the since is invalid.
+    fn multisig_args(&self) -> Option<([u8; BLAKE160_SIZE], Option<Since>)> {
+        if self.kind() != AddressKind::MultiSig {
+            return None;
+        }
+        let args = self.to_script().into_parts().2;
+        let since = match args.len() {
+            BLAKE160_SIZE => None,
+            len if len == BLAKE160_SIZE + SINCE_SIZE => {
+                let mut bytes = [0u8; SINCE_SIZE];
+                bytes.copy_from_slice(&args[BLAKE160_SIZE..]);
+                Since::from_le_bytes(bytes).ok()
+            }
+            _ => return None,
+        };
+        let mut hash = [0u8; BLAKE160_SIZE];
+        hash.copy_from_slice(&args[..BLAKE160_SIZE]);
+        Some((hash, since))
+    }
+
+    /// Checks if the address is encoded in a deprecated format.
+    pub fn is_deprecated(&self) -> bool {
+        self.format.is_deprecated()
+    }
+
+    /// Converts the address into the target format.
+    ///
+    /// The code hash index is resolved to the well-known code hash on the network when convert
+    /// a short format address into a full format address.
+    /// Returns an error if the target format is short but no short format exists.
+    pub fn convert(&self, format: AddressFormat) -> Result<Self> {
+        self.clone().into_builder().format(format).build()
+    }
+
+    /// Encodes the address into a string.
+    pub fn encode(&self) -> Result<String> {
+        let hrp = self.network.value();
+        let (data, variant) = self.payload()?;
+        let address = bech32::encode(hrp, data.to_base32(), variant).map_err(Error::Bech32)?;
+        if address.len() > ADDRESS_MAX_LENGTH {
+            Err(Error::InvalidLength(address.len()))
+        } else {
+            Ok(address)
+        }
+    }
+
+    /// Same as [`encode`](#method.encode), an alias of `to_string()` which never panics.
+    pub fn try_to_string(&self) -> Result<String> {
+        self.encode()
+    }
+
+    pub fn into_builder(self) -> AddressBuilder {
+        let Self {
+            network,
+            format,
+            code_hash,
+            args,
+        } = self;
+        AddressBuilder {
+            network,
+            format: Some(format),
+            code_hash,
+            args,
+        }
+    }
+}
+
+impl PartialEq for Address {
+    fn eq(&self, other: &Self) -> bool {
+        self.network == other.network && self.same_lock(other)
+    }
+}
+
+impl Eq for Address {}
+
+impl hash::Hash for Address {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.network.hash(state);
+        hash::Hash::hash(&self.to_script(), state);
+    }
+}
+
+impl PartialOrd for Address {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Address {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.network
+            .cmp(&other.network)
+            .then_with(|| self.to_script().cmp(&other.to_script()))
+    }
+}
+
+impl fmt::Debug for Address {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Address {{")?;
+        write!(f, " network: {}", self.network)?;
+        write!(f, " , format: {:?}", self.format)?;
+        write!(f, " , code_hash: {}", self.code_hash)?;
+        write!(f, " , args: {}", self.args)?;
+        write!(f, " }}")
+    }
+}
+
+/// The length is checked when building, so a built address is always displayed in full.
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let hrp = self.network.value();
+        let (data, variant) = self.payload().map_err(|_| fmt::Error)?;
+        bech32::encode_to_fmt(f, hrp, data.to_base32(), variant).map_err(|_| fmt::Error)?
+    }
+}
+
+impl<'a> From<&'a Address> for Script {
+    fn from(address: &'a Address) -> Self {
+        address.to_script()
+    }
+}
+
+impl convert::TryFrom<(Script, Network)> for Address {
+    type Error = Error;
+    fn try_from((script, network): (Script, Network)) -> Result<Self> {
+        Self::from_script(script, network)
     }
 }
 
 impl str::FromStr for Address {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self> {
+        if s.len() > ADDRESS_MAX_LENGTH {
+            return Err(Error::InvalidLength(s.len()));
+        }
         bech32::decode(s)
             .map_err(Error::Bech32)
-            .and_then(|(ref hrp, ref base32)| {
-                let network = Network::from_value(&hrp)?;
-                let bytes = Vec::<u8>::from_base32(&base32).map_err(Error::Bech32)?;
+            .and_then(|(ref hrp, ref base32, variant)| {
+                let network = Network::from_value(hrp)?;
+                let bytes = Vec::<u8>::from_base32(base32).map_err(Error::Bech32)?;
                 let mut offset = 0;
                 let mut data = &bytes[offset..];
                 if data.is_empty() {
                     Err(Error::InvalidDataSince(offset))
                 } else {
-                    let format = PayloadFormat::from_value(data[0])?;
+                    let payload_format = PayloadFormat::from_value(data[0])?;
+                    if payload_format.variant() != variant {
+                        return Err(Error::MismatchedVariant(payload_format));
+                    }
                     offset += 1;
                     data = &bytes[offset..];
-                    let (code_hash, args) = match format {
+                    let (code_hash, args) = match payload_format {
                         PayloadFormat::Short => {
                             if data.is_empty() {
                                 return Err(Error::InvalidDataSince(offset));
@@ -377,17 +778,44 @@ impl str::FromStr for Address {
                             let index = CodeHashIndex::from_value(data[0])?;
                             offset += 1;
                             data = &bytes[offset..];
-                            let args = if data.len() == BLAKE160_SIZE {
-                                Ok(Args::Simple(data.to_owned()))
-                            } else {
-                                Err(Error::ShortFormatArgs)
+                            let args = match index {
+                                CodeHashIndex::AnyoneCanPay => {
+                                    Args::anyone_can_pay_from_slice(data)
+                                        .map_err(|_| Error::ShortFormatArgs)
+                                }
+                                _ => {
+                                    if data.len() == BLAKE160_SIZE {
+                                        Ok(Args::Simple(data.to_owned()))
+                                    } else {
+                                        Err(Error::ShortFormatArgs)
+                                    }
+                                }
                             }?;
                             (CodeHash::Index(index), args)
                         }
-                        PayloadFormat::Full(hash_type) => {
+                        PayloadFormat::Full => {
+                            if data.len() < CODE_HASH_SIZE + 1 {
+                                return Err(Error::InvalidDataSince(offset));
+                            }
+                            let mut content = [0u8; CODE_HASH_SIZE];
+                            content.copy_from_slice(&data[..CODE_HASH_SIZE]);
+                            let hash_type = CodeHashType::from_value(data[CODE_HASH_SIZE])?;
+                            offset += CODE_HASH_SIZE + 1;
+                            data = &bytes[offset..];
+                            (
+                                CodeHash::Data { hash_type, content },
+                                Args::Simple(data.to_owned()),
+                            )
+                        }
+                        PayloadFormat::FullData | PayloadFormat::FullType => {
                             if data.len() < CODE_HASH_SIZE {
                                 return Err(Error::InvalidDataSince(offset));
                             }
+                            let hash_type = if payload_format == PayloadFormat::FullData {
+                                CodeHashType::Data
+                            } else {
+                                CodeHashType::Type
+                            };
                             let mut content = [0u8; CODE_HASH_SIZE];
                             content.copy_from_slice(&data[..CODE_HASH_SIZE]);
                             offset += CODE_HASH_SIZE;
@@ -400,6 +828,7 @@ impl str::FromStr for Address {
                     };
                     AddressBuilder::default()
                         .network(network)
+                        .format(payload_format.address_format())
                         .code_hash(code_hash)
                         .args(args)
                         .build()
@@ -438,7 +867,7 @@ impl AddressBuilder {
         first_n_required: u8,
         threshold: u8,
         contents: Vec<[u8; BLAKE160_SIZE]>,
-        since: Option<[u8; SINCE_SIZE]>,
+        since: Option<Since>,
     ) -> Self {
         self.args = Args::MultiSig {
             version,
@@ -450,12 +879,77 @@ impl AddressBuilder {
         self
     }
 
+    pub fn args_multisig_config(self, config: MultiSigConfig, since: Option<Since>) -> Self {
+        let version = config.version();
+        let first_n_required = config.first_n_required();
+        let threshold = config.threshold();
+        let contents = config.pubkey_hashes().to_owned();
+        self.args_multisig(version, first_n_required, threshold, contents, since)
+    }
+
+    pub fn args_anyone_can_pay(
+        mut self,
+        pubkey_hash: [u8; BLAKE160_SIZE],
+        min_ckb: Option<u8>,
+        min_udt: Option<u8>,
+    ) -> Self {
+        self.args = Args::AnyoneCanPay {
+            pubkey_hash,
+            min_ckb,
+            min_udt,
+        };
+        self
+    }
+
+    pub fn args_omnilock(mut self, args: OmniLockArgs) -> Self {
+        self.args = Args::Omnilock(args);
+        self
+    }
+
     pub fn build(self) -> Result<Address> {
         let Self {
             network,
+            format,
             code_hash,
             args,
         } = self;
+        if let CodeHash::Index(index) = code_hash {
+            if index.resolve(network).is_none() {
+                return Err(Error::UnavailableCodeHashIndex(index, network));
+            }
+        }
+        // Convert the code hash if the format is specified.
+        let (format, code_hash) = match code_hash {
+            CodeHash::Index(index) => match format.unwrap_or(AddressFormat::Short) {
+                AddressFormat::Short => (AddressFormat::Short, code_hash),
+                format => {
+                    let (hash_type, content) = index.resolve(network).unwrap_or_else(|| {
+                        unreachable!("code hash index is checked before");
+                    });
+                    (format, CodeHash::Data { hash_type, content })
+                }
+            },
+            CodeHash::Data {
+                hash_type,
+                ref content,
+            } => match format.unwrap_or_default() {
+                AddressFormat::Short => {
+                    let index = network
+                        .spec()
+                        .code_hash_index(hash_type, content)
+                        .ok_or(Error::NoShortFormat)?;
+                    (AddressFormat::Short, CodeHash::Index(index))
+                }
+                format => (format, code_hash),
+            },
+        };
+        if let CodeHash::Data { hash_type, .. } = code_hash {
+            if format == AddressFormat::DeprecatedFull
+                && hash_type.deprecated_payload_format().is_none()
+            {
+                return Err(Error::UnsupportedFormat(format));
+            }
+        }
         match code_hash {
             CodeHash::Index(index) => match index {
                 CodeHashIndex::Secp256k1Blake160 => match args {
@@ -476,26 +970,56 @@ impl AddressBuilder {
                             Err(Error::ShortFormatArgs)
                         }
                     }
-                    Args::MultiSig { .. } => Ok(()),
+                    Args::MultiSig { since: None, .. } => Ok(()),
+                    Args::MultiSig { .. } => Err(Error::ShortFormatArgs),
+                    _ => Err(Error::MultiSigArgs),
+                },
+                CodeHashIndex::AnyoneCanPay => match args {
+                    Args::Simple(ref content) => {
+                        if content.len() >= BLAKE160_SIZE && content.len() <= ACP_ARGS_MAX_SIZE {
+                            Ok(())
+                        } else {
+                            Err(Error::ShortFormatArgs)
+                        }
+                    }
+                    Args::AnyoneCanPay { .. } => Ok(()),
+                    _ => Err(Error::AnyoneCanPayArgs),
                 },
             },
             CodeHash::Data { .. } => Ok(()),
         }?;
         if let Args::MultiSig {
+            version,
             first_n_required,
             threshold,
             ref contents,
             ..
         } = args
         {
-            if first_n_required > threshold || threshold > contents.len() as u8 {
-                return Err(Error::MultiSigArgs);
-            }
+            multisig::check(version, first_n_required, threshold, contents)?;
         };
-        Ok(Address {
+        if let Args::AnyoneCanPay {
+            min_ckb: None,
+            min_udt: Some(_),
+            ..
+        } = args
+        {
+            return Err(Error::AnyoneCanPayArgs);
+        };
+        let address = Address {
             network,
+            format,
             code_hash,
             args,
-        })
+        };
+        // Only the typed args are checked, unknown Omnilock layouts are still valid addresses.
+        if let Args::Omnilock(_) = address.args {
+            if address.kind() != AddressKind::Omnilock {
+                return Err(Error::OmniLockArgs);
+            }
+        }
+        // Ensure that the address could be encoded within the max length, so the `Display`
+        // of a built address never produces a string which could not be parsed back.
+        address.encode().map(|_| address)
     }
 }
@@ -0,0 +1,249 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+use property::Property;
+
+use super::{
+    error::{Error, Result},
+    Address, AddressBuilder, AddressFormat, CodeHashIndex, Network, Since, BLAKE160_SIZE,
+};
+use crate::{blake2b, secp256k1::PublicKey, utilities};
+
+/// The size of the header: version, first n required, threshold and pubkey hashes count.
+const HEADER_SIZE: usize = 4;
+/// The only version which is supported by the multisig lock.
+pub const MULTISIG_VERSION: u8 = 0;
+/// The max count of the pubkey hashes in a multisig script.
+pub const MULTISIG_KEYS_MAX: usize = std::u8::MAX as usize;
+
+/// MultiSig Config
+///
+/// The multisig script which is put into the witness, the multisig lock args are the blake160
+/// hash of it (with an optional since).
+#[derive(Property, Clone, PartialEq, Eq, Hash)]
+#[property(get(public), set(disable), mut(disable))]
+pub struct MultiSigConfig {
+    #[property(get(type = "copy"))]
+    version: u8,
+    #[property(get(type = "copy"))]
+    first_n_required: u8,
+    #[property(get(type = "copy"))]
+    threshold: u8,
+    pubkey_hashes: Vec<[u8; BLAKE160_SIZE]>,
+}
+
+pub(super) fn serialize_into(
+    buf: &mut Vec<u8>,
+    version: u8,
+    first_n_required: u8,
+    threshold: u8,
+    pubkey_hashes: &[[u8; BLAKE160_SIZE]],
+) {
+    buf.reserve(HEADER_SIZE + BLAKE160_SIZE * pubkey_hashes.len());
+    buf.push(version);
+    buf.push(first_n_required);
+    buf.push(threshold);
+    buf.push(pubkey_hashes.len() as u8);
+    for pubkey_hash in pubkey_hashes {
+        buf.extend_from_slice(&pubkey_hash[..]);
+    }
+}
+
+/// Checks the multisig script as the on-chain multisig lock does.
+pub(super) fn check(
+    version: u8,
+    first_n_required: u8,
+    threshold: u8,
+    pubkey_hashes: &[[u8; BLAKE160_SIZE]],
+) -> Result<()> {
+    if version != MULTISIG_VERSION {
+        return Err(Error::UnsupportedMultiSigVersion(version));
+    }
+    if pubkey_hashes.len() > MULTISIG_KEYS_MAX {
+        return Err(Error::TooManyMultiSigKeys(pubkey_hashes.len()));
+    }
+    if threshold == 0 {
+        return Err(Error::ZeroMultiSigThreshold);
+    }
+    if usize::from(threshold) > pubkey_hashes.len() {
+        return Err(Error::MultiSigThresholdTooLarge(
+            threshold,
+            pubkey_hashes.len(),
+        ));
+    }
+    if first_n_required > threshold {
+        return Err(Error::MultiSigFirstNTooLarge(first_n_required, threshold));
+    }
+    for (i, pubkey_hash) in pubkey_hashes.iter().enumerate() {
+        if pubkey_hashes[..i].contains(pubkey_hash) {
+            return Err(Error::DuplicateMultiSigKey(utilities::hex_string(
+                &pubkey_hash[..],
+            )));
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Debug for MultiSigConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MultiSigConfig {{")?;
+        write!(f, " version: {}", self.version)?;
+        write!(f, " , first_n: {}", self.first_n_required)?;
+        write!(f, " , threshold: {}", self.threshold)?;
+        write!(f, " , pubkey_hashes: [")?;
+        for (i, pubkey_hash) in self.pubkey_hashes.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", utilities::hex_string(&pubkey_hash[..]))?;
+        }
+        write!(f, "] }}")
+    }
+}
+
+impl MultiSigConfig {
+    pub fn new(
+        version: u8,
+        first_n_required: u8,
+        threshold: u8,
+        pubkey_hashes: Vec<[u8; BLAKE160_SIZE]>,
+    ) -> Result<Self> {
+        check(version, first_n_required, threshold, &pubkey_hashes)?;
+        Ok(Self {
+            version,
+            first_n_required,
+            threshold,
+            pubkey_hashes,
+        })
+    }
+
+    /// Creates a multisig config from the public keys of the co-signers.
+    pub fn from_public_keys(
+        version: u8,
+        first_n_required: u8,
+        threshold: u8,
+        pubkeys: &[PublicKey],
+    ) -> Result<Self> {
+        let pubkey_hashes = pubkeys
+            .iter()
+            .map(|pubkey| {
+                let mut pubkey_hash = [0u8; BLAKE160_SIZE];
+                pubkey_hash.copy_from_slice(pubkey.pkhash_blake160().as_slice());
+                pubkey_hash
+            })
+            .collect();
+        Self::new(version, first_n_required, threshold, pubkey_hashes)
+    }
+
+    /// Creates a multisig config with the pubkey hashes in the canonical order.
+    ///
+    /// The duplicate pubkey hashes are removed (the first one is kept), then the pubkey hashes
+    /// which are not in the first n required are sorted.
+    pub fn canonical(
+        version: u8,
+        first_n_required: u8,
+        threshold: u8,
+        pubkey_hashes: Vec<[u8; BLAKE160_SIZE]>,
+    ) -> Result<Self> {
+        let mut canonical: Vec<[u8; BLAKE160_SIZE]> = Vec::with_capacity(pubkey_hashes.len());
+        for pubkey_hash in pubkey_hashes {
+            if !canonical.contains(&pubkey_hash) {
+                canonical.push(pubkey_hash);
+            }
+        }
+        let required = usize::from(first_n_required).min(canonical.len());
+        canonical[required..].sort_unstable();
+        Self::new(version, first_n_required, threshold, canonical)
+    }
+
+    /// Checks if the pubkey hashes are in the canonical order.
+    pub fn is_canonical(&self) -> bool {
+        let required = usize::from(self.first_n_required);
+        self.pubkey_hashes[required..]
+            .windows(2)
+            .all(|pair| pair[0] < pair[1])
+    }
+
+    /// Parses the multisig script.
+    pub fn from_slice(data: &[u8]) -> Result<Self> {
+        if data.len() < HEADER_SIZE
+            || data.len() != HEADER_SIZE + BLAKE160_SIZE * usize::from(data[3])
+        {
+            return Err(Error::InvalidMultiSigConfig);
+        }
+        let pubkey_hashes = data[HEADER_SIZE..]
+            .chunks(BLAKE160_SIZE)
+            .map(|chunk| {
+                let mut pubkey_hash = [0u8; BLAKE160_SIZE];
+                pubkey_hash.copy_from_slice(chunk);
+                pubkey_hash
+            })
+            .collect();
+        Self::new(data[0], data[1], data[2], pubkey_hashes)
+    }
+
+    /// Serializes the multisig script.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        serialize_into(
+            &mut buf,
+            self.version,
+            self.first_n_required,
+            self.threshold,
+            &self.pubkey_hashes,
+        );
+        buf
+    }
+
+    /// The blake160 hash of the multisig script.
+    pub fn hash(&self) -> [u8; BLAKE160_SIZE] {
+        blake2b::blake160(self.serialize())
+    }
+
+    /// Creates the multisig address in the full format on the network.
+    pub fn address(&self, network: Network, since: Option<Since>) -> Result<Address> {
+        AddressBuilder::default()
+            .network(network)
+            .format(AddressFormat::Full)
+            .code_hash_by_index(CodeHashIndex::Secp256k1MultiSig)
+            .args_multisig_config(self.clone(), since)
+            .build()
+    }
+
+    /// Checks if the address is a multisig address for this config.
+    ///
+    /// The args of the address should be the hash of the config, with or without a since.
+    pub fn matches(&self, address: &Address) -> bool {
+        address.multisig_hash() == Some(self.hash())
+    }
+}
+
+impl Address {
+    /// Creates a multisig address in the full format from the public keys of the co-signers.
+    ///
+    /// The multisig config is returned too, it is required by the signers for the witness.
+    pub fn multisig(
+        network: Network,
+        first_n_required: u8,
+        threshold: u8,
+        pubkeys: &[PublicKey],
+        since: Option<Since>,
+    ) -> Result<(Self, MultiSigConfig)> {
+        let config = MultiSigConfig::from_public_keys(
+            MULTISIG_VERSION,
+            first_n_required,
+            threshold,
+            pubkeys,
+        )?;
+        config
+            .address(network, since)
+            .map(|address| (address, config))
+    }
+}
@@ -0,0 +1,227 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::{fmt, sync::RwLock};
+
+use lazy_static::lazy_static;
+use property::Property;
+
+use super::{
+    error::{Error, Result},
+    CodeHash, CodeHashIndex, CodeHashType, CODE_HASH_SIZE,
+};
+use crate::script::known::{self, KnownScript, SystemScript};
+
+/// The max length of the human-readable part of bech32.
+const HRP_MAX_SIZE: usize = 83;
+
+lazy_static! {
+    static ref MAINNET: NetworkSpec =
+        NetworkSpec::new("ckb", "mainnet").known_scripts_from(known::mainnet());
+    static ref TESTNET: NetworkSpec =
+        NetworkSpec::new("ckt", "testnet").known_scripts_from(known::testnet());
+    static ref REGISTRY: RwLock<Vec<&'static NetworkSpec>> = RwLock::new(Vec::new());
+}
+
+/// CKB Network
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Network {
+    Main,
+    Test,
+    Custom(CustomNetwork),
+}
+
+impl Default for Network {
+    fn default() -> Self {
+        Network::Main
+    }
+}
+
+/// A handle of a custom network, which is only created by registering a network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CustomNetwork(usize);
+
+/// Network Specification
+///
+/// The human-readable part of addresses, the well-known code hashes for
+/// the short format indexes and the deployed system scripts on a network.
+#[derive(Property, Debug, Clone, PartialEq, Eq)]
+#[property(get(public), set(disable), mut(disable))]
+pub struct NetworkSpec {
+    hrp: String,
+    name: String,
+    #[property(get(disable))]
+    code_hashes: Vec<(CodeHashIndex, CodeHashType, [u8; CODE_HASH_SIZE])>,
+    known_scripts: Vec<KnownScript>,
+}
+
+impl NetworkSpec {
+    pub fn new(hrp: &str, name: &str) -> Self {
+        Self {
+            hrp: hrp.to_owned(),
+            name: name.to_owned(),
+            code_hashes: Vec::new(),
+            known_scripts: Vec::new(),
+        }
+    }
+
+    pub fn code_hash_by_index(
+        mut self,
+        index: CodeHashIndex,
+        hash_type: CodeHashType,
+        content: [u8; CODE_HASH_SIZE],
+    ) -> Self {
+        self.code_hashes.retain(|(i, _, _)| *i != index);
+        self.code_hashes.push((index, hash_type, content));
+        self
+    }
+
+    pub fn known_script(mut self, script: KnownScript) -> Self {
+        self.known_scripts
+            .retain(|s| s.system_script() != script.system_script());
+        self.known_scripts.push(script);
+        self
+    }
+
+    // Adds the known scripts, and uses them as the code hashes for the short format indexes.
+    fn known_scripts_from(self, scripts: &[KnownScript]) -> Self {
+        scripts.iter().cloned().fold(self, |spec, script| {
+            let index = match script.system_script() {
+                SystemScript::Secp256k1Blake160 => Some(CodeHashIndex::Secp256k1Blake160),
+                SystemScript::Secp256k1MultiSig => Some(CodeHashIndex::Secp256k1MultiSig),
+                SystemScript::AnyoneCanPay => Some(CodeHashIndex::AnyoneCanPay),
+                _ => None,
+            };
+            let spec = match (index, script.to_code_hash()) {
+                (Some(index), CodeHash::Data { hash_type, content }) => {
+                    spec.code_hash_by_index(index, hash_type, content)
+                }
+                _ => spec,
+            };
+            spec.known_script(script)
+        })
+    }
+
+    /// Returns the well-known code hash for the short format index.
+    pub fn code_hash(&self, index: CodeHashIndex) -> Option<(CodeHashType, [u8; CODE_HASH_SIZE])> {
+        self.code_hashes
+            .iter()
+            .find(|(i, _, _)| *i == index)
+            .map(|(_, hash_type, content)| (*hash_type, *content))
+    }
+
+    /// Returns the short format index for the well-known code hash.
+    pub fn code_hash_index(
+        &self,
+        hash_type: CodeHashType,
+        content: &[u8],
+    ) -> Option<CodeHashIndex> {
+        self.code_hashes
+            .iter()
+            .find(|(_, t, c)| *t == hash_type && c[..] == content[..])
+            .map(|(index, _, _)| *index)
+    }
+
+    /// Returns the deployed system script which has the code hash.
+    pub fn known_script_by_code_hash(
+        &self,
+        hash_type: CodeHashType,
+        content: &[u8],
+    ) -> Option<&KnownScript> {
+        self.known_scripts
+            .iter()
+            .find(|script| script.matches(hash_type, content))
+    }
+
+    /// Returns the deployment of the system script.
+    pub fn known_script_of(&self, system_script: SystemScript) -> Option<&KnownScript> {
+        self.known_scripts
+            .iter()
+            .find(|script| script.system_script() == system_script)
+    }
+
+    fn check_hrp(&self) -> Result<()> {
+        let hrp = &self.hrp;
+        if hrp.is_empty()
+            || hrp.len() > HRP_MAX_SIZE
+            || !hrp
+                .bytes()
+                .all(|c| (33..=126).contains(&c) && !c.is_ascii_uppercase())
+        {
+            Err(Error::InvalidNetwork(hrp.to_owned()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Network {
+    /// Registers a custom network.
+    ///
+    /// Registering the same specification again returns the same network.
+    pub fn register(spec: NetworkSpec) -> Result<Self> {
+        spec.check_hrp()?;
+        let mut registry = REGISTRY.write().unwrap_or_else(|err| err.into_inner());
+        if let Ok(network) = Self::find(&registry, spec.hrp()) {
+            if Self::spec_in(&registry, network) == &spec {
+                return Ok(network);
+            } else {
+                return Err(Error::DuplicateNetwork(spec.hrp));
+            }
+        }
+        let network = Self::Custom(CustomNetwork(registry.len()));
+        registry.push(Box::leak(Box::new(spec)));
+        Ok(network)
+    }
+
+    fn find(registry: &[&'static NetworkSpec], value: &str) -> Result<Self> {
+        if value == MAINNET.hrp() {
+            Ok(Self::Main)
+        } else if value == TESTNET.hrp() {
+            Ok(Self::Test)
+        } else {
+            registry
+                .iter()
+                .position(|spec| spec.hrp() == value)
+                .map(|id| Self::Custom(CustomNetwork(id)))
+                .ok_or_else(|| Error::UnknownNetwork(value.to_owned()))
+        }
+    }
+
+    fn spec_in(registry: &[&'static NetworkSpec], network: Self) -> &'static NetworkSpec {
+        match network {
+            Self::Main => &MAINNET,
+            Self::Test => &TESTNET,
+            Self::Custom(CustomNetwork(id)) => registry[id],
+        }
+    }
+
+    pub fn spec(self) -> &'static NetworkSpec {
+        if let Self::Custom(_) = self {
+            let registry = REGISTRY.read().unwrap_or_else(|err| err.into_inner());
+            Self::spec_in(&registry, self)
+        } else {
+            Self::spec_in(&[], self)
+        }
+    }
+
+    pub fn value(self) -> &'static str {
+        self.spec().hrp()
+    }
+
+    pub fn from_value(value: &str) -> Result<Self> {
+        let registry = REGISTRY.read().unwrap_or_else(|err| err.into_inner());
+        Self::find(&registry, value)
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.spec().name())
+    }
+}
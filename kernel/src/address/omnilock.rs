@@ -0,0 +1,353 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+use property::Property;
+
+use super::{
+    error::{Error, Result},
+    Address, AddressBuilder, AddressFormat, AddressKind, MultiSigConfig, Network, Since,
+    BLAKE160_SIZE, SINCE_SIZE,
+};
+use crate::{keccak, script::SystemScript, secp256k1::PublicKey, sha256, utilities, PubKeyHash};
+
+/// The size of the auth: a flag and a 20 bytes content.
+pub const OMNI_AUTH_SIZE: usize = 1 + BLAKE160_SIZE;
+
+const ADMIN_LIST_SIZE: usize = 32;
+const ACP_SIZE: usize = 2;
+const SUPPLY_SIZE: usize = 32;
+
+const OMNI_FLAG_ADMIN_LIST: u8 = 0x01;
+const OMNI_FLAG_ACP: u8 = 0x02;
+const OMNI_FLAG_TIME_LOCK: u8 = 0x04;
+const OMNI_FLAG_SUPPLY: u8 = 0x08;
+const OMNI_FLAGS_ALL: u8 =
+    OMNI_FLAG_ADMIN_LIST | OMNI_FLAG_ACP | OMNI_FLAG_TIME_LOCK | OMNI_FLAG_SUPPLY;
+
+/// Omnilock Auth Flag
+///
+/// The flag defines how the auth content is computed and how the signature is verified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OmniAuthFlag {
+    CkbSecp256k1 = 0x00,
+    Ethereum = 0x01,
+    Eos = 0x02,
+    Tron = 0x03,
+    Bitcoin = 0x04,
+    Dogecoin = 0x05,
+    MultiSig = 0x06,
+    OwnerLock = 0xfc,
+    Exec = 0xfd,
+    Dl = 0xfe,
+}
+
+/// Omnilock Auth
+#[derive(Property, Clone, PartialEq, Eq, Hash)]
+#[property(get(public), set(disable), mut(disable))]
+pub struct OmniAuth {
+    #[property(get(type = "copy"))]
+    flag: OmniAuthFlag,
+    content: [u8; BLAKE160_SIZE],
+}
+
+/// Omnilock Args
+///
+/// The auth, then the omni flags and the optional fields which are enabled by the flags.
+#[derive(Property, Clone, PartialEq, Eq, Hash)]
+#[property(get(public), set(disable), mut(disable))]
+pub struct OmniLockArgs {
+    auth: OmniAuth,
+    /// The type id of the admin list cell.
+    admin_list: Option<[u8; 32]>,
+    /// The minimum amounts exponents of CKB and UDT, as the anyone-can-pay lock.
+    #[property(get(type = "copy"))]
+    anyone_can_pay: Option<(u8, u8)>,
+    #[property(get(type = "copy"))]
+    time_lock: Option<Since>,
+    /// The type script hash of the info cell for the supply mode.
+    supply: Option<[u8; 32]>,
+}
+
+impl OmniAuthFlag {
+    pub fn value(self) -> u8 {
+        self as u8
+    }
+
+    pub fn from_value(value: u8) -> Result<Self> {
+        match value {
+            0x00 => Ok(Self::CkbSecp256k1),
+            0x01 => Ok(Self::Ethereum),
+            0x02 => Ok(Self::Eos),
+            0x03 => Ok(Self::Tron),
+            0x04 => Ok(Self::Bitcoin),
+            0x05 => Ok(Self::Dogecoin),
+            0x06 => Ok(Self::MultiSig),
+            0xfc => Ok(Self::OwnerLock),
+            0xfd => Ok(Self::Exec),
+            0xfe => Ok(Self::Dl),
+            v => Err(Error::UnknownOmniAuthFlag(v)),
+        }
+    }
+}
+
+impl fmt::Debug for OmniAuth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "OmniAuth {{")?;
+        write!(f, " flag: {:?}", self.flag)?;
+        write!(
+            f,
+            " , content: {}",
+            utilities::hex_string(&self.content[..])
+        )?;
+        write!(f, " }}")
+    }
+}
+
+impl OmniAuth {
+    pub fn new(flag: OmniAuthFlag, content: [u8; BLAKE160_SIZE]) -> Self {
+        Self { flag, content }
+    }
+
+    /// The auth of the CKB secp256k1 blake160 signature.
+    pub fn from_pubkey_hash(pkhash: &PubKeyHash) -> Self {
+        let mut content = [0u8; BLAKE160_SIZE];
+        content.copy_from_slice(pkhash.as_slice());
+        Self::new(OmniAuthFlag::CkbSecp256k1, content)
+    }
+
+    /// The auth of the CKB secp256k1 blake160 signature.
+    pub fn from_public_key(pubkey: &PublicKey) -> Self {
+        Self::from_pubkey_hash(&pubkey.pkhash_blake160())
+    }
+
+    /// The auth of the Ethereum signature, the content is the Ethereum address.
+    pub fn ethereum(pubkey: &PublicKey) -> Self {
+        let data = pubkey.serialize_uncompressed();
+        Self::new(OmniAuthFlag::Ethereum, keccak::keccak160(&data[1..]))
+    }
+
+    /// The auth of the Bitcoin signature, the content is the hash160 of the compressed public key.
+    pub fn bitcoin(pubkey: &PublicKey) -> Self {
+        Self::new(
+            OmniAuthFlag::Bitcoin,
+            sha256::hash160(&pubkey.serialize()[..]),
+        )
+    }
+
+    /// The auth of the CKB multisig, the content is the hash of the multisig script.
+    pub fn multisig(config: &MultiSigConfig) -> Self {
+        Self::new(OmniAuthFlag::MultiSig, config.hash())
+    }
+
+    /// The auth of an owner lock, the content is the first 20 bytes of the lock hash.
+    pub fn owner_lock(lock_hash: &[u8; 32]) -> Self {
+        let mut content = [0u8; BLAKE160_SIZE];
+        content.copy_from_slice(&lock_hash[..BLAKE160_SIZE]);
+        Self::new(OmniAuthFlag::OwnerLock, content)
+    }
+
+    pub fn from_slice(data: &[u8]) -> Result<Self> {
+        if data.len() != OMNI_AUTH_SIZE {
+            return Err(Error::OmniLockArgs);
+        }
+        let flag = OmniAuthFlag::from_value(data[0])?;
+        let mut content = [0u8; BLAKE160_SIZE];
+        content.copy_from_slice(&data[1..]);
+        Ok(Self::new(flag, content))
+    }
+
+    pub fn serialize(&self) -> [u8; OMNI_AUTH_SIZE] {
+        let mut data = [0u8; OMNI_AUTH_SIZE];
+        data[0] = self.flag.value();
+        data[1..].copy_from_slice(&self.content[..]);
+        data
+    }
+}
+
+impl fmt::Debug for OmniLockArgs {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "OmniLockArgs {{")?;
+        write!(f, " auth: {:?}", self.auth)?;
+        if let Some(ref admin_list) = self.admin_list {
+            write!(
+                f,
+                " , admin_list: {}",
+                utilities::hex_string(&admin_list[..])
+            )?;
+        }
+        if let Some((min_ckb, min_udt)) = self.anyone_can_pay {
+            write!(f, " , anyone_can_pay: ({}, {})", min_ckb, min_udt)?;
+        }
+        if let Some(since) = self.time_lock {
+            write!(f, " , time_lock: {}", since)?;
+        }
+        if let Some(ref supply) = self.supply {
+            write!(f, " , supply: {}", utilities::hex_string(&supply[..]))?;
+        }
+        write!(f, " }}")
+    }
+}
+
+impl From<OmniAuth> for OmniLockArgs {
+    fn from(auth: OmniAuth) -> Self {
+        Self::new(auth)
+    }
+}
+
+impl OmniLockArgs {
+    pub fn new(auth: OmniAuth) -> Self {
+        Self {
+            auth,
+            admin_list: None,
+            anyone_can_pay: None,
+            time_lock: None,
+            supply: None,
+        }
+    }
+
+    /// Enables the administrator mode with the type id of the admin list cell.
+    pub fn with_admin_list(mut self, type_id: [u8; 32]) -> Self {
+        self.admin_list = Some(type_id);
+        self
+    }
+
+    /// Enables the anyone-can-pay mode with the minimum amounts exponents.
+    pub fn with_anyone_can_pay(mut self, min_ckb: u8, min_udt: u8) -> Self {
+        self.anyone_can_pay = Some((min_ckb, min_udt));
+        self
+    }
+
+    /// Enables the time-lock mode.
+    pub fn with_time_lock(mut self, since: Since) -> Self {
+        self.time_lock = Some(since);
+        self
+    }
+
+    /// Enables the supply mode with the type script hash of the info cell.
+    pub fn with_supply(mut self, type_script_hash: [u8; 32]) -> Self {
+        self.supply = Some(type_script_hash);
+        self
+    }
+
+    /// The omni flags for the enabled modes.
+    pub fn omni_flags(&self) -> u8 {
+        let mut flags = 0;
+        if self.admin_list.is_some() {
+            flags |= OMNI_FLAG_ADMIN_LIST;
+        }
+        if self.anyone_can_pay.is_some() {
+            flags |= OMNI_FLAG_ACP;
+        }
+        if self.time_lock.is_some() {
+            flags |= OMNI_FLAG_TIME_LOCK;
+        }
+        if self.supply.is_some() {
+            flags |= OMNI_FLAG_SUPPLY;
+        }
+        flags
+    }
+
+    pub fn from_slice(data: &[u8]) -> Result<Self> {
+        if data.len() <= OMNI_AUTH_SIZE {
+            return Err(Error::OmniLockArgs);
+        }
+        let auth = OmniAuth::from_slice(&data[..OMNI_AUTH_SIZE])?;
+        let flags = data[OMNI_AUTH_SIZE];
+        if flags & !OMNI_FLAGS_ALL != 0 {
+            return Err(Error::OmniLockArgs);
+        }
+        let mut args = Self::new(auth);
+        let mut rest = &data[OMNI_AUTH_SIZE + 1..];
+        let mut take = |size: usize| -> Result<&[u8]> {
+            if rest.len() < size {
+                return Err(Error::OmniLockArgs);
+            }
+            let (field, remaining) = rest.split_at(size);
+            rest = remaining;
+            Ok(field)
+        };
+        if flags & OMNI_FLAG_ADMIN_LIST != 0 {
+            let mut type_id = [0u8; ADMIN_LIST_SIZE];
+            type_id.copy_from_slice(take(ADMIN_LIST_SIZE)?);
+            args.admin_list = Some(type_id);
+        }
+        if flags & OMNI_FLAG_ACP != 0 {
+            let field = take(ACP_SIZE)?;
+            args.anyone_can_pay = Some((field[0], field[1]));
+        }
+        if flags & OMNI_FLAG_TIME_LOCK != 0 {
+            let mut bytes = [0u8; SINCE_SIZE];
+            bytes.copy_from_slice(take(SINCE_SIZE)?);
+            args.time_lock = Some(Since::from_le_bytes(bytes)?);
+        }
+        if flags & OMNI_FLAG_SUPPLY != 0 {
+            let mut type_script_hash = [0u8; SUPPLY_SIZE];
+            type_script_hash.copy_from_slice(take(SUPPLY_SIZE)?);
+            args.supply = Some(type_script_hash);
+        }
+        if rest.is_empty() {
+            Ok(args)
+        } else {
+            Err(Error::OmniLockArgs)
+        }
+    }
+
+    pub fn serialize_into(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.auth.serialize()[..]);
+        buf.push(self.omni_flags());
+        if let Some(ref admin_list) = self.admin_list {
+            buf.extend_from_slice(&admin_list[..]);
+        }
+        if let Some((min_ckb, min_udt)) = self.anyone_can_pay {
+            buf.push(min_ckb);
+            buf.push(min_udt);
+        }
+        if let Some(since) = self.time_lock {
+            buf.extend_from_slice(&since.to_le_bytes()[..]);
+        }
+        if let Some(ref supply) = self.supply {
+            buf.extend_from_slice(&supply[..]);
+        }
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.serialize_into(&mut buf);
+        buf
+    }
+
+    /// Creates the Omnilock address in the full format on the network.
+    pub fn address(&self, network: Network) -> Result<Address> {
+        let script = network
+            .spec()
+            .known_script_of(SystemScript::Omnilock)
+            .ok_or(Error::UnavailableKnownScript(
+                SystemScript::Omnilock,
+                network,
+            ))?;
+        AddressBuilder::default()
+            .network(network)
+            .format(AddressFormat::Full)
+            .code_hash(script.to_code_hash())
+            .args_omnilock(self.clone())
+            .build()
+    }
+}
+
+impl Address {
+    /// Returns the args of an Omnilock address.
+    pub fn omnilock_args(&self) -> Option<OmniLockArgs> {
+        if self.kind() == AddressKind::Omnilock {
+            OmniLockArgs::from_slice(self.to_script().args()).ok()
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,234 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::{fmt, str};
+
+use super::{
+    error::{Error, Result},
+    SINCE_SIZE,
+};
+
+const RELATIVE_FLAG: u64 = 1 << 63;
+const METRIC_MASK: u64 = 0b11 << 61;
+const METRIC_BLOCK_NUMBER: u64 = 0b00 << 61;
+const METRIC_EPOCH: u64 = 0b01 << 61;
+const METRIC_TIMESTAMP: u64 = 0b10 << 61;
+const RESERVED_MASK: u64 = 0b1_1111 << 56;
+const VALUE_MASK: u64 = (1 << 56) - 1;
+
+const EPOCH_NUMBER_MAX: u64 = (1 << 24) - 1;
+const EPOCH_FRACTION_MAX: u64 = (1 << 16) - 1;
+
+/// Since Metric
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SinceMetric {
+    BlockNumber(u64),
+    EpochNumberWithFraction {
+        number: u64,
+        index: u64,
+        length: u64,
+    },
+    /// The median time of the past blocks, in seconds.
+    Timestamp(u64),
+}
+
+/// Since
+///
+/// The lock time for an input, the rules are defined in RFC 0017 of CKB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Since(u64);
+
+impl Since {
+    pub fn block_number(relative: bool, number: u64) -> Result<Self> {
+        if number > VALUE_MASK {
+            return Err(Error::InvalidSince(format!(
+                "block number {} is overflow",
+                number
+            )));
+        }
+        Self::from_value(Self::flag(relative) | METRIC_BLOCK_NUMBER | number)
+    }
+
+    pub fn epoch(relative: bool, number: u64, index: u64, length: u64) -> Result<Self> {
+        if number > EPOCH_NUMBER_MAX || index > EPOCH_FRACTION_MAX || length > EPOCH_FRACTION_MAX {
+            return Err(Error::InvalidSince(format!(
+                "epoch {}+{}/{} is overflow",
+                number, index, length
+            )));
+        }
+        let value = number | (index << 24) | (length << 40);
+        Self::from_value(Self::flag(relative) | METRIC_EPOCH | value)
+    }
+
+    pub fn timestamp(relative: bool, seconds: u64) -> Result<Self> {
+        if seconds > VALUE_MASK {
+            return Err(Error::InvalidSince(format!(
+                "timestamp {} is overflow",
+                seconds
+            )));
+        }
+        Self::from_value(Self::flag(relative) | METRIC_TIMESTAMP | seconds)
+    }
+
+    fn flag(relative: bool) -> u64 {
+        if relative {
+            RELATIVE_FLAG
+        } else {
+            0
+        }
+    }
+
+    /// Creates a since from the raw value, and checks it by the rules.
+    pub fn from_value(value: u64) -> Result<Self> {
+        if value & RESERVED_MASK != 0 {
+            return Err(Error::InvalidSince(
+                "reserved bits should be zeros".to_owned(),
+            ));
+        }
+        if value & METRIC_MASK == METRIC_MASK {
+            return Err(Error::InvalidSince("unknown metric".to_owned()));
+        }
+        let since = Self(value);
+        if let SinceMetric::EpochNumberWithFraction { index, length, .. } = since.metric() {
+            // The fraction should be less than one, or be zero.
+            if !((length == 0 && index == 0) || (length > 0 && index < length)) {
+                return Err(Error::InvalidSince(format!(
+                    "epoch fraction {}/{} is not well formed",
+                    index, length
+                )));
+            }
+        }
+        Ok(since)
+    }
+
+    pub fn value(self) -> u64 {
+        self.0
+    }
+
+    pub fn from_le_bytes(bytes: [u8; SINCE_SIZE]) -> Result<Self> {
+        Self::from_value(u64::from_le_bytes(bytes))
+    }
+
+    pub fn to_le_bytes(self) -> [u8; SINCE_SIZE] {
+        self.0.to_le_bytes()
+    }
+
+    pub fn is_relative(self) -> bool {
+        self.0 & RELATIVE_FLAG != 0
+    }
+
+    pub fn metric(self) -> SinceMetric {
+        let value = self.0 & VALUE_MASK;
+        match self.0 & METRIC_MASK {
+            METRIC_BLOCK_NUMBER => SinceMetric::BlockNumber(value),
+            METRIC_EPOCH => SinceMetric::EpochNumberWithFraction {
+                number: value & EPOCH_NUMBER_MAX,
+                index: (value >> 24) & EPOCH_FRACTION_MAX,
+                length: (value >> 40) & EPOCH_FRACTION_MAX,
+            },
+            METRIC_TIMESTAMP => SinceMetric::Timestamp(value),
+            _ => unreachable!("metric is checked when created"),
+        }
+    }
+}
+
+fn parse_number(s: &str) -> Result<u64> {
+    s.parse()
+        .map_err(|_| Error::InvalidSince(format!("invalid number \"{}\"", s)))
+}
+
+fn parse_epoch(s: &str) -> Result<(u64, u64, u64)> {
+    if let Some(pos) = s.find('+') {
+        let number = parse_number(&s[..pos])?;
+        let fraction = &s[pos + 1..];
+        let pos = fraction
+            .find('/')
+            .ok_or_else(|| Error::InvalidSince(format!("invalid epoch \"{}\"", s)))?;
+        let index = parse_number(&fraction[..pos])?;
+        let length = parse_number(&fraction[pos + 1..])?;
+        Ok((number, index, length))
+    } else {
+        parse_number(s).map(|number| (number, 0, 1))
+    }
+}
+
+impl fmt::Display for Since {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let relative = self.is_relative();
+        match self.metric() {
+            SinceMetric::BlockNumber(number) => {
+                if relative {
+                    write!(f, "relative {} blocks", number)
+                } else {
+                    write!(f, "block {}", number)
+                }
+            }
+            SinceMetric::EpochNumberWithFraction {
+                number,
+                index,
+                length,
+            } => {
+                if relative {
+                    write!(f, "relative ")?;
+                }
+                write!(f, "epoch {}+{}/{}", number, index, length)
+            }
+            SinceMetric::Timestamp(seconds) => {
+                if relative {
+                    write!(f, "relative {} seconds", seconds)
+                } else {
+                    write!(f, "timestamp {}", seconds)
+                }
+            }
+        }
+    }
+}
+
+/// Parses a since from a string.
+///
+/// Supported formats (an optional prefix "relative" or "absolute" is allowed):
+/// - "block 100", "100 blocks"
+/// - "epoch 1234+5/10", "epoch 1234"
+/// - "timestamp 1600000000", "3600 seconds"
+/// - the raw value in hexadecimal, such as "0x2000000000000001"
+impl str::FromStr for Since {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim().to_lowercase();
+        if s.starts_with("0x") {
+            return u64::from_str_radix(&s[2..], 16)
+                .map_err(|_| Error::InvalidSince(format!("invalid value \"{}\"", s)))
+                .and_then(Self::from_value);
+        }
+        let mut words = s.split_whitespace().collect::<Vec<_>>();
+        let relative = match words.first() {
+            Some(&"relative") => {
+                words.remove(0);
+                true
+            }
+            Some(&"absolute") => {
+                words.remove(0);
+                false
+            }
+            _ => false,
+        };
+        match words[..] {
+            ["block", value] | [value, "block"] | [value, "blocks"] => {
+                Self::block_number(relative, parse_number(value)?)
+            }
+            ["epoch", value] | [value, "epoch"] | [value, "epochs"] => {
+                let (number, index, length) = parse_epoch(value)?;
+                Self::epoch(relative, number, index, length)
+            }
+            ["timestamp", value] | [value, "second"] | [value, "seconds"] => {
+                Self::timestamp(relative, parse_number(value)?)
+            }
+            _ => Err(Error::InvalidSince(format!("unknown format \"{}\"", s))),
+        }
+    }
+}
@@ -52,7 +52,7 @@ fn test_short_for_secp256k1_multisig() {
 }
 
 #[test]
-fn test_full_for_simple() {
+fn test_deprecated_full_for_simple() {
     let code_hash = u8_slice!("0x9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8");
     let args = u8_slice!("0xb39bbc0b3673c7d36450bc14cfcdad2d559c6c64");
     let expected = "ckb1\
@@ -60,6 +60,7 @@ fn test_full_for_simple() {
                     86dj9p0q5elx66t24n3kxgj53qks";
     let addr = super::AddressBuilder::default()
         .network(super::Network::Main)
+        .format(super::AddressFormat::DeprecatedFull)
         .code_hash_by_data(super::CodeHashType::Type, *code_hash)
         .args_simple(args.to_vec())
         .build()
@@ -72,22 +73,922 @@ fn test_full_for_simple() {
 }
 
 #[test]
-fn test_full_for_multisig() {
+fn test_deprecated_full_for_multisig() {
     let code_hash = u8_slice!("0x5c5069eb0857efc65e1bca0c07df34c31663b3622fd3876c876320fc9634e2a8");
     let args = u8_slice!("0x4146af6d67742cca87a9b0d1d3eb070e7a544e1c");
     let since = u8_slice!("0x5605008403080720");
     let expected = "ckb1\
                     q3w9q60tppt7l3j7r09qcp7lxnp3vcanvgha8pmvsa3jplykxn32s8ulyn4d0l8\
                     ztr8fzy4hzjmnn0uufyknj4s9qzzqxzq8yqd5ry6k";
+    let addr = super::AddressBuilder::default()
+        .network(super::Network::Main)
+        .format(super::AddressFormat::DeprecatedFull)
+        .code_hash_by_data(super::CodeHashType::Type, *code_hash)
+        .args_multisig(
+            0,
+            0,
+            1,
+            vec![*args],
+            Some(super::Since::from_le_bytes(*since).unwrap()),
+        )
+        .build()
+        .unwrap();
+    let actual = addr.to_string();
+    assert_eq!(expected, actual);
+    let addr = super::Address::from_str(&actual).unwrap();
+    let actual = addr.to_string();
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn test_full_for_simple() {
+    let code_hash = u8_slice!("0x9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8");
+    let args = u8_slice!("0xb39bbc0b3673c7d36450bc14cfcdad2d559c6c64");
+    let expected = "ckb1\
+                    qzda0cr08m85hc8jlnfp3zer7xulejywt49kt2rr0vthywaa50xwsqdnnw7qkdnnclfkg59uzn8umt\
+                    fd2kwxceqxwquc4";
     let addr = super::AddressBuilder::default()
         .network(super::Network::Main)
         .code_hash_by_data(super::CodeHashType::Type, *code_hash)
-        .args_multisig(0, 0, 1, vec![*args], Some(*since))
+        .args_simple(args.to_vec())
         .build()
         .unwrap();
+    assert_eq!(addr.format(), super::AddressFormat::Full);
     let actual = addr.to_string();
     assert_eq!(expected, actual);
     let addr = super::Address::from_str(&actual).unwrap();
+    assert_eq!(addr.format(), super::AddressFormat::Full);
     let actual = addr.to_string();
     assert_eq!(expected, actual);
 }
+
+#[test]
+fn test_full_with_mismatched_variant() {
+    use bech32::ToBase32;
+    let code_hash = u8_slice!("0x9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8");
+    let args = u8_slice!("0xb39bbc0b3673c7d36450bc14cfcdad2d559c6c64");
+    let mut data = vec![super::PayloadFormat::Full.value()];
+    data.extend_from_slice(&code_hash[..]);
+    data.push(super::CodeHashType::Type.value());
+    data.extend_from_slice(&args[..]);
+    let encoded = bech32::encode("ckb", data.to_base32(), bech32::Variant::Bech32).unwrap();
+    let result = super::Address::from_str(&encoded);
+    assert_matches!(
+        result,
+        Err(super::Error::MismatchedVariant(super::PayloadFormat::Full))
+    );
+}
+
+#[test]
+fn test_full_for_all_hash_types() {
+    let code_hash = u8_slice!("0x9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8");
+    let args = u8_slice!("0xb39bbc0b3673c7d36450bc14cfcdad2d559c6c64");
+    for (hash_type, value) in &[
+        (super::CodeHashType::Data, 0x00),
+        (super::CodeHashType::Type, 0x01),
+        (super::CodeHashType::Data1, 0x02),
+        (super::CodeHashType::Data2, 0x04),
+    ] {
+        assert_eq!(hash_type.value(), *value);
+        assert_eq!(super::CodeHashType::from_value(*value).unwrap(), *hash_type);
+        let addr = super::AddressBuilder::default()
+            .network(super::Network::Test)
+            .code_hash_by_data(*hash_type, *code_hash)
+            .args_simple(args.to_vec())
+            .build()
+            .unwrap();
+        let expected = addr.to_string();
+        let addr = super::Address::from_str(&expected).unwrap();
+        match addr.code_hash() {
+            super::CodeHash::Data {
+                hash_type: actual, ..
+            } => assert_eq!(actual, hash_type),
+            _ => panic!("code hash should be data"),
+        }
+        assert_eq!(expected, addr.to_string());
+    }
+    assert!(super::CodeHashType::from_value(0x03).is_err());
+}
+
+#[test]
+fn test_deprecated_full_for_data1() {
+    let code_hash = u8_slice!("0x9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8");
+    let args = u8_slice!("0xb39bbc0b3673c7d36450bc14cfcdad2d559c6c64");
+    let result = super::AddressBuilder::default()
+        .format(super::AddressFormat::DeprecatedFull)
+        .code_hash_by_data(super::CodeHashType::Data1, *code_hash)
+        .args_simple(args.to_vec())
+        .build();
+    assert_matches!(
+        result,
+        Err(super::Error::UnsupportedFormat(
+            super::AddressFormat::DeprecatedFull
+        ))
+    );
+}
+
+#[test]
+fn test_short_for_anyone_can_pay() {
+    use bech32::FromBase32;
+    let pkh = u8_slice!("0xb39bbc0b3673c7d36450bc14cfcdad2d559c6c64");
+    let test = |min_ckb: Option<u8>, min_udt: Option<u8>| {
+        let addr = super::AddressBuilder::default()
+            .network(super::Network::Test)
+            .code_hash_by_index(super::CodeHashIndex::AnyoneCanPay)
+            .args_anyone_can_pay(*pkh, min_ckb, min_udt)
+            .build()
+            .unwrap();
+        let encoded = addr.to_string();
+        let (_, base32, _) = bech32::decode(&encoded).unwrap();
+        let payload = Vec::<u8>::from_base32(&base32).unwrap();
+        let mut expected = vec![0x01, 0x02];
+        expected.extend_from_slice(&pkh[..]);
+        expected.extend(min_ckb);
+        expected.extend(min_udt);
+        assert_eq!(payload, expected);
+        let addr = super::Address::from_str(&encoded).unwrap();
+        match addr.args() {
+            super::Args::AnyoneCanPay {
+                pubkey_hash,
+                min_ckb: actual_min_ckb,
+                min_udt: actual_min_udt,
+            } => {
+                assert_eq!(pubkey_hash, pkh);
+                assert_eq!(*actual_min_ckb, min_ckb);
+                assert_eq!(*actual_min_udt, min_udt);
+            }
+            _ => panic!("args should be anyone-can-pay"),
+        }
+        assert_eq!(encoded, addr.to_string());
+    };
+    test(None, None);
+    test(Some(9), None);
+    test(Some(9), Some(2));
+    let result = super::AddressBuilder::default()
+        .code_hash_by_index(super::CodeHashIndex::AnyoneCanPay)
+        .args_anyone_can_pay(*pkh, None, Some(2))
+        .build();
+    assert_matches!(result, Err(super::Error::AnyoneCanPayArgs));
+    let result = super::AddressBuilder::default()
+        .code_hash_by_index(super::CodeHashIndex::AnyoneCanPay)
+        .args_simple(vec![0u8; super::ACP_ARGS_MAX_SIZE + 1])
+        .build();
+    assert_matches!(result, Err(super::Error::ShortFormatArgs));
+}
+
+#[test]
+fn test_custom_network() {
+    let code_hash = u8_slice!("0x32064a14ce10d95d4b7343054cc19d73b25b16ae61a6c681011ca781a60c7923");
+    let pkh = u8_slice!("0xb39bbc0b3673c7d36450bc14cfcdad2d559c6c64");
+    let spec = super::NetworkSpec::new("ckd", "devnet").code_hash_by_index(
+        super::CodeHashIndex::Secp256k1Blake160,
+        super::CodeHashType::Type,
+        *code_hash,
+    );
+    let network = super::Network::register(spec.clone()).unwrap();
+    assert_eq!(network.value(), "ckd");
+    assert_eq!(network.to_string(), "devnet");
+    assert_eq!(super::Network::register(spec).unwrap(), network);
+    assert_eq!(super::Network::from_value("ckd").unwrap(), network);
+    let addr = crate::PubKeyHash::from_secp256k1_blake160(*pkh)
+        .address(network)
+        .unwrap();
+    let encoded = addr.to_string();
+    assert!(encoded.starts_with("ckd1"));
+    let addr = super::Address::from_str(&encoded).unwrap();
+    assert_eq!(*addr.network(), network);
+    let script = addr.to_script();
+    assert_eq!(script.code_hash(), code_hash);
+    assert_eq!(script.args(), &pkh.to_vec());
+    let result = super::AddressBuilder::default()
+        .network(network)
+        .code_hash_by_index(super::CodeHashIndex::AnyoneCanPay)
+        .args_anyone_can_pay(*pkh, None, None)
+        .build();
+    assert_matches!(
+        result,
+        Err(super::Error::UnavailableCodeHashIndex(
+            super::CodeHashIndex::AnyoneCanPay,
+            _
+        ))
+    );
+}
+
+#[test]
+fn test_register_network_errors() {
+    let spec = super::NetworkSpec::new("ckb", "fake mainnet");
+    let result = super::Network::register(spec);
+    assert_matches!(result, Err(super::Error::DuplicateNetwork(_)));
+    for hrp in &["", "CKX", "ck x"] {
+        let spec = super::NetworkSpec::new(hrp, "invalid");
+        let result = super::Network::register(spec);
+        assert_matches!(result, Err(super::Error::InvalidNetwork(_)));
+    }
+    let result = super::Network::from_value("ckz");
+    assert_matches!(result, Err(super::Error::UnknownNetwork(_)));
+}
+
+#[test]
+fn test_encode_without_panic() {
+    let pkh = u8_slice!("0x4146af6d67742cca87a9b0d1d3eb070e7a544e1c");
+    let since = u8_slice!("0x5605008403080720");
+    let result = super::AddressBuilder::default()
+        .code_hash_by_index(super::CodeHashIndex::Secp256k1MultiSig)
+        .args_multisig(
+            0,
+            0,
+            1,
+            vec![*pkh],
+            Some(super::Since::from_le_bytes(*since).unwrap()),
+        )
+        .build();
+    assert_matches!(result, Err(super::Error::ShortFormatArgs));
+    let addr = super::AddressBuilder::default()
+        .code_hash_by_index(super::CodeHashIndex::Secp256k1MultiSig)
+        .args_multisig(0, 0, 1, vec![*pkh], None)
+        .build()
+        .unwrap();
+    let encoded = addr.encode().unwrap();
+    assert_eq!(encoded, addr.to_string());
+    assert_eq!(encoded, addr.try_to_string().unwrap());
+}
+
+#[test]
+fn test_convert_formats() {
+    let short = "ckb1qyqt8xaupvm8837nv3gtc9x0ekkj64vud3jqfwyw5v";
+    let full = "ckb1qzda0cr08m85hc8jlnfp3zer7xulejywt49kt2rr0vthywaa50xwsqdnnw7qkdnnclfkg59uzn8umt\
+                fd2kwxceqxwquc4";
+    let deprecated_full = "ckb1qjda0cr08m85hc8jlnfp3zer7xulejywt49kt2rr0vthywaa50xw3vumhs9nvu7\
+                           86dj9p0q5elx66t24n3kxgj53qks";
+    for input in &[short, full, deprecated_full] {
+        let addr = super::Address::from_str(input).unwrap();
+        assert_eq!(addr.is_deprecated(), *input != full);
+        let converted = addr.convert(super::AddressFormat::Short).unwrap();
+        assert_eq!(converted.to_string(), short);
+        assert!(converted.is_deprecated());
+        let converted = addr.convert(super::AddressFormat::Full).unwrap();
+        assert_eq!(converted.to_string(), full);
+        assert!(!converted.is_deprecated());
+        let converted = addr.convert(super::AddressFormat::DeprecatedFull).unwrap();
+        assert_eq!(converted.to_string(), deprecated_full);
+        assert!(converted.is_deprecated());
+    }
+    let addr = super::AddressBuilder::default()
+        .format(super::AddressFormat::Full)
+        .code_hash_by_index(super::CodeHashIndex::Secp256k1Blake160)
+        .args_simple(u8_slice!("0xb39bbc0b3673c7d36450bc14cfcdad2d559c6c64").to_vec())
+        .build()
+        .unwrap();
+    assert_eq!(addr.to_string(), full);
+}
+
+#[test]
+fn test_convert_without_short_format() {
+    let code_hash = u8_slice!("0x5c5069eb0857efc65e1bca0c07df34c31663b3622fd3876c876320fc9634e2a8");
+    let args = u8_slice!("0x4146af6d67742cca87a9b0d1d3eb070e7a544e1c");
+    let since = u8_slice!("0x5605008403080720");
+    let addr = super::AddressBuilder::default()
+        .code_hash_by_data(super::CodeHashType::Type, *code_hash)
+        .args_multisig(
+            0,
+            0,
+            1,
+            vec![*args],
+            Some(super::Since::from_le_bytes(*since).unwrap()),
+        )
+        .build()
+        .unwrap();
+    let result = addr.convert(super::AddressFormat::Short);
+    assert_matches!(result, Err(super::Error::ShortFormatArgs));
+    let addr = super::AddressBuilder::default()
+        .code_hash_by_data(super::CodeHashType::Data, *code_hash)
+        .args_simple(args.to_vec())
+        .build()
+        .unwrap();
+    let result = addr.convert(super::AddressFormat::Short);
+    assert_matches!(result, Err(super::Error::NoShortFormat));
+}
+
+#[test]
+fn test_semantic_equality() {
+    use std::collections::HashMap;
+    let short = super::Address::from_str("ckb1qyqt8xaupvm8837nv3gtc9x0ekkj64vud3jqfwyw5v").unwrap();
+    let full = super::Address::from_str(
+        "ckb1qzda0cr08m85hc8jlnfp3zer7xulejywt49kt2rr0vthywaa50xwsqdnnw7qkdnnclfkg59uzn8umt\
+         fd2kwxceqxwquc4",
+    )
+    .unwrap();
+    let deprecated_full = super::Address::from_str(
+        "ckb1qjda0cr08m85hc8jlnfp3zer7xulejywt49kt2rr0vthywaa50xw3vumhs9nvu7\
+         86dj9p0q5elx66t24n3kxgj53qks",
+    )
+    .unwrap();
+    let testnet = short
+        .clone()
+        .into_builder()
+        .network(super::Network::Test)
+        .build()
+        .unwrap();
+    let other = super::Address::from_str("ckb1qyqp8eqad7ffy42ezmchkjyz54rhcqf8q9pqrn323p").unwrap();
+    assert_eq!(short, full);
+    assert_eq!(short, deprecated_full);
+    assert_eq!(short.cmp(&full), std::cmp::Ordering::Equal);
+    assert_ne!(short, testnet);
+    assert!(short.same_lock(&testnet));
+    assert_ne!(short, other);
+    assert!(!short.same_lock(&other));
+    let mut map = HashMap::new();
+    map.insert(short, 1);
+    map.insert(full, 2);
+    map.insert(deprecated_full.clone(), 3);
+    map.insert(testnet, 4);
+    map.insert(other, 5);
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.get(&deprecated_full), Some(&3));
+}
+
+#[test]
+fn test_multisig_config() {
+    let pkh1 = u8_slice!("0xbd07d9f32bce34d27152a6a0391d324f79aab854");
+    let pkh2 = u8_slice!("0x094ee28566dff02a012a66505822a2fd67d668fb");
+    let pkh3 = u8_slice!("0x4643c241e59e81b7876527ebff23dfb24cf16482");
+    let since = u8_slice!("0x5605008403080720");
+    let config = super::MultiSigConfig::new(0, 1, 2, vec![*pkh1, *pkh2, *pkh3]).unwrap();
+    let serialized = config.serialize();
+    assert_eq!(&serialized[..4], &[0, 1, 2, 3]);
+    assert_eq!(serialized.len(), 4 + 20 * 3);
+    assert_eq!(
+        super::MultiSigConfig::from_slice(&serialized).unwrap(),
+        config
+    );
+    assert!(super::MultiSigConfig::from_slice(&serialized[1..]).is_err());
+    let short = super::Address::from_str("ckb1qyq5lv479ewscx3ms620sv34pgeuz6zagaaqklhtgg").unwrap();
+    assert!(config.matches(&short));
+    let addr = super::AddressBuilder::default()
+        .code_hash_by_index(super::CodeHashIndex::Secp256k1MultiSig)
+        .args_multisig_config(
+            config.clone(),
+            Some(super::Since::from_le_bytes(*since).unwrap()),
+        )
+        .format(super::AddressFormat::Full)
+        .build()
+        .unwrap();
+    assert_eq!(addr.args().multisig_config(), Some(config.clone()));
+    let addr = super::Address::from_str(&addr.to_string()).unwrap();
+    assert!(config.matches(&addr));
+    let other = super::MultiSigConfig::new(0, 0, 2, vec![*pkh1, *pkh2, *pkh3]).unwrap();
+    assert!(!other.matches(&addr));
+    let not_multisig = super::AddressBuilder::default()
+        .code_hash_by_index(super::CodeHashIndex::Secp256k1Blake160)
+        .args_simple(config.hash().to_vec())
+        .build()
+        .unwrap();
+    assert!(!config.matches(&not_multisig));
+}
+
+#[test]
+fn test_since() {
+    use super::{Since, SinceMetric};
+    let since = Since::from_le_bytes(*u8_slice!("0x5605008403080720")).unwrap();
+    assert!(!since.is_relative());
+    assert_eq!(
+        since.metric(),
+        SinceMetric::EpochNumberWithFraction {
+            number: 1366,
+            index: 900,
+            length: 1800
+        }
+    );
+    assert_eq!(since.to_string(), "epoch 1366+900/1800");
+    assert_eq!(Since::from_str("epoch 1366+900/1800").unwrap(), since);
+    assert_eq!(since.to_le_bytes(), *u8_slice!("0x5605008403080720"));
+    let test = |input: &str, expected: Since, display: &str| {
+        let since = Since::from_str(input).unwrap();
+        assert_eq!(since, expected);
+        assert_eq!(since.to_string(), display);
+        assert_eq!(Since::from_str(display).unwrap(), expected);
+        assert_eq!(Since::from_value(since.value()).unwrap(), expected);
+    };
+    let expected = Since::block_number(true, 100).unwrap();
+    test("relative 100 blocks", expected, "relative 100 blocks");
+    test("Relative block 100", expected, "relative 100 blocks");
+    test("0x8000000000000064", expected, "relative 100 blocks");
+    let expected = Since::block_number(false, 100).unwrap();
+    test("absolute 100 blocks", expected, "block 100");
+    let expected = Since::epoch(false, 1234, 5, 10).unwrap();
+    test("epoch 1234+5/10", expected, "epoch 1234+5/10");
+    let expected = Since::epoch(true, 2, 0, 1).unwrap();
+    test("relative 2 epochs", expected, "relative epoch 2+0/1");
+    let expected = Since::timestamp(false, 1_600_000_000).unwrap();
+    test("timestamp 1600000000", expected, "timestamp 1600000000");
+    let expected = Since::timestamp(true, 3600).unwrap();
+    test("relative 3600 seconds", expected, "relative 3600 seconds");
+    assert!(Since::epoch(false, 1, 10, 10).is_err());
+    assert!(Since::epoch(false, 1 << 24, 0, 1).is_err());
+    assert!(Since::block_number(false, 1 << 56).is_err());
+    assert!(Since::from_value(0x6000_0000_0000_0000).is_err());
+    assert!(Since::from_value(0x0100_0000_0000_0000).is_err());
+    for input in &["", "block", "100 days", "epoch 1+2", "relative block -1"] {
+        assert!(Since::from_str(input).is_err());
+    }
+}
+
+#[test]
+fn test_kind_and_accessors() {
+    use super::{AddressFormat, AddressKind, Since};
+    let pkh = u8_slice!("0xb39bbc0b3673c7d36450bc14cfcdad2d559c6c64");
+    let single =
+        super::Address::from_str("ckb1qyqt8xaupvm8837nv3gtc9x0ekkj64vud3jqfwyw5v").unwrap();
+    let acp = super::AddressBuilder::default()
+        .code_hash_by_index(super::CodeHashIndex::AnyoneCanPay)
+        .args_anyone_can_pay(*pkh, Some(9), None)
+        .build()
+        .unwrap();
+    for addr in &[single, acp] {
+        for format in &[AddressFormat::Short, AddressFormat::Full] {
+            let addr = addr.convert(*format).unwrap();
+            assert_eq!(addr.pubkey_hash().unwrap().as_slice(), &pkh[..]);
+            assert!(addr.multisig_hash().is_none());
+            assert!(addr.since().is_none());
+        }
+    }
+    assert_eq!(
+        super::Address::from_str("ckb1qyqt8xaupvm8837nv3gtc9x0ekkj64vud3jqfwyw5v")
+            .unwrap()
+            .kind(),
+        AddressKind::SingleSig
+    );
+
+    let config = super::MultiSigConfig::new(0, 0, 1, vec![*pkh]).unwrap();
+    let since = Since::epoch(true, 6, 0, 1).unwrap();
+    let multisig = super::AddressBuilder::default()
+        .code_hash_by_index(super::CodeHashIndex::Secp256k1MultiSig)
+        .args_multisig_config(config.clone(), None)
+        .build()
+        .unwrap();
+    assert_eq!(multisig.kind(), AddressKind::MultiSig);
+    assert_eq!(multisig.multisig_hash(), Some(config.hash()));
+    assert!(multisig.since().is_none());
+    assert!(multisig.pubkey_hash().is_none());
+    let locked = super::AddressBuilder::default()
+        .code_hash_by_index(super::CodeHashIndex::Secp256k1MultiSig)
+        .format(AddressFormat::Full)
+        .args_multisig_config(config.clone(), Some(since))
+        .build()
+        .unwrap();
+    let locked = super::Address::from_str(&locked.to_string()).unwrap();
+    assert_matches!(locked.code_hash(), super::CodeHash::Data { .. });
+    assert_eq!(locked.kind(), AddressKind::MultiSig);
+    assert_eq!(locked.multisig_hash(), Some(config.hash()));
+    assert_eq!(locked.since(), Some(since));
+
+    let cheque = super::AddressBuilder::default()
+        .network(super::Network::Test)
+        .code_hash_by_data(
+            super::CodeHashType::Type,
+            *u8_slice!("0x60d5f39efce409c587cb9ea359cefdead650ca128f0bd9cb3855348f98c70d5b"),
+        )
+        .args_simple(vec![0u8; 40])
+        .build()
+        .unwrap();
+    assert_eq!(cheque.kind(), AddressKind::Cheque);
+    assert!(cheque.pubkey_hash().is_none());
+    let unknown = super::AddressBuilder::default()
+        .code_hash_by_data(super::CodeHashType::Data, [0u8; 32])
+        .args_simple(pkh.to_vec())
+        .build()
+        .unwrap();
+    assert_eq!(unknown.kind(), AddressKind::Unknown);
+    assert!(unknown.pubkey_hash().is_none());
+}
+
+#[test]
+fn test_multisig_invalid_since() {
+    let config = super::MultiSigConfig::new(0, 0, 1, vec![[0x11; 20]]).unwrap();
+    let addr = super::AddressBuilder::default()
+        .code_hash_by_index(super::CodeHashIndex::Secp256k1MultiSig)
+        .args_multisig_config(config.clone(), None)
+        .build()
+        .unwrap();
+    let (code_hash, hash_type, mut args) = addr.to_script().into_parts();
+    args.extend_from_slice(&[0xff; 8]);
+    let script = crate::Script::new(code_hash, hash_type, args);
+    let addr = super::Address::from_script(script, super::Network::Main).unwrap();
+    assert_eq!(addr.kind(), super::AddressKind::MultiSig);
+    assert_eq!(addr.multisig_hash(), Some(config.hash()));
+    assert_eq!(addr.since(), None);
+}
+
+#[test]
+fn test_cheque() {
+    use super::{AddressKind, ChequeArgs};
+    let receiver =
+        super::Address::from_str("ckb1qyqt8xaupvm8837nv3gtc9x0ekkj64vud3jqfwyw5v").unwrap();
+    let sender =
+        super::Address::from_str("ckb1qyqp8eqad7ffy42ezmchkjyz54rhcqf8q9pqrn323p").unwrap();
+    let cheque = super::Address::cheque(&receiver, &sender).unwrap();
+    assert_eq!(cheque.format(), super::AddressFormat::Full);
+    assert_eq!(cheque.kind(), AddressKind::Cheque);
+    let cheque = super::Address::from_str(&cheque.to_string()).unwrap();
+    let args = cheque.cheque_args().unwrap();
+    assert_eq!(
+        args.receiver_lock_hash(),
+        &receiver.to_script().hash()[..20]
+    );
+    assert_eq!(args.sender_lock_hash(), &sender.to_script().hash()[..20]);
+    assert!(args.is_receiver(&receiver));
+    assert!(args.is_receiver(&receiver.convert(super::AddressFormat::Full).unwrap()));
+    assert!(!args.is_receiver(&sender));
+    assert!(args.is_sender(&sender));
+    assert_eq!(
+        ChequeArgs::new(&receiver.to_script().hash(), &sender.to_script().hash()),
+        args
+    );
+    assert_eq!(ChequeArgs::from_slice(&args.serialize()).unwrap(), args);
+    assert!(receiver.cheque_args().is_none());
+
+    let testnet = receiver
+        .clone()
+        .into_builder()
+        .network(super::Network::Test)
+        .build()
+        .unwrap();
+    assert!(super::Address::cheque(&testnet, &sender).is_err());
+    assert!(ChequeArgs::from_slice(&[0u8; 39]).is_err());
+    // Args in other layouts are still valid cheque addresses, but without typed args.
+    for len in &[3, 39] {
+        let (code_hash, hash_type, _) = cheque.to_script().into_parts();
+        let script = crate::Script::new(code_hash, hash_type, vec![0u8; *len]);
+        let addr = super::Address::from_script(script, super::Network::Main).unwrap();
+        assert_eq!(addr.kind(), AddressKind::Cheque);
+        assert!(addr.cheque_args().is_none());
+        let decoded = super::Address::from_str(&addr.to_string()).unwrap();
+        assert_eq!(decoded, addr);
+    }
+    let network = super::Network::register(super::NetworkSpec::new("ckcheque", "cheque")).unwrap();
+    assert!(args.address(network).is_err());
+}
+
+#[test]
+fn test_omnilock() {
+    use super::{AddressKind, OmniAuth, OmniAuthFlag, OmniLockArgs, Since};
+    use crate::secp256k1::SecretKey;
+    let sk = SecretKey::from_slice(u8_slice!(
+        "0x0000000000000000000000000000000000000000000000000000000000000001"
+    ))
+    .unwrap();
+    let pk = sk.public_key();
+    let auth = OmniAuth::ethereum(&pk);
+    assert_eq!(auth.flag(), OmniAuthFlag::Ethereum);
+    assert_eq!(
+        auth.content(),
+        u8_slice!("0x7e5f4552091a69125d5dfcb7b8c2659029395bdf")
+    );
+    let auth = OmniAuth::bitcoin(&pk);
+    assert_eq!(
+        auth.content(),
+        u8_slice!("0x751e76e8199196d454941c45d1b3a323f1433bd6")
+    );
+    let auth = OmniAuth::from_public_key(&pk);
+    assert_eq!(auth.flag(), OmniAuthFlag::CkbSecp256k1);
+    assert_eq!(auth.content(), pk.pkhash_blake160().as_slice());
+    assert_eq!(OmniAuth::from_slice(&auth.serialize()).unwrap(), auth);
+
+    let args = OmniLockArgs::new(auth.clone());
+    let data = args.serialize();
+    assert_eq!(data.len(), 22);
+    assert_eq!(data[0], 0x00);
+    assert_eq!(data[21], 0x00);
+    let addr = args.address(super::Network::Main).unwrap();
+    assert_eq!(addr.kind(), AddressKind::Omnilock);
+    let addr = super::Address::from_str(&addr.to_string()).unwrap();
+    assert_eq!(addr.omnilock_args(), Some(args));
+
+    let since = Since::block_number(true, 100).unwrap();
+    let args = OmniLockArgs::new(auth)
+        .with_admin_list([0x11; 32])
+        .with_anyone_can_pay(9, 0)
+        .with_time_lock(since)
+        .with_supply([0x22; 32]);
+    assert_eq!(args.omni_flags(), 0x0f);
+    let data = args.serialize();
+    assert_eq!(data.len(), 22 + 32 + 2 + 8 + 32);
+    assert_eq!(OmniLockArgs::from_slice(&data).unwrap(), args);
+    let addr = args.address(super::Network::Test).unwrap();
+    assert_eq!(addr.omnilock_args().unwrap().time_lock(), Some(since));
+
+    for invalid in &[
+        &data[..21],
+        &data[..data.len() - 1],
+        &[&data[..], &[0u8][..]].concat()[..],
+    ] {
+        assert!(OmniLockArgs::from_slice(invalid).is_err());
+    }
+    let mut unknown_flags = data.clone();
+    unknown_flags[21] |= 0x10;
+    assert!(OmniLockArgs::from_slice(&unknown_flags).is_err());
+    let mut unknown_auth = data.clone();
+    unknown_auth[0] = 0x12;
+    assert!(OmniLockArgs::from_slice(&unknown_auth).is_err());
+    // Unknown layouts are still valid Omnilock addresses, but without typed args.
+    let omnilock = addr.code_hash().to_owned();
+    for unknown in &[unknown_flags, unknown_auth] {
+        let addr = super::AddressBuilder::default()
+            .network(super::Network::Test)
+            .code_hash(omnilock)
+            .args_simple(unknown.clone())
+            .build()
+            .unwrap();
+        assert_eq!(addr.kind(), AddressKind::Omnilock);
+        assert_eq!(addr.omnilock_args(), None);
+        let decoded = super::Address::from_str(&addr.to_string()).unwrap();
+        assert_eq!(decoded, addr);
+    }
+    assert!(super::AddressBuilder::default()
+        .code_hash_by_index(super::CodeHashIndex::Secp256k1Blake160)
+        .args_omnilock(args.clone())
+        .build()
+        .is_err());
+    assert_matches!(
+        super::AddressBuilder::default()
+            .code_hash_by_data(super::CodeHashType::Type, [0x33; 32])
+            .args_omnilock(args)
+            .build(),
+        Err(super::error::Error::OmniLockArgs)
+    );
+}
+
+#[test]
+fn test_time_lock() {
+    use super::{AddressFormat, AddressKind, Since, TimeLock};
+    use crate::PubKeyHash;
+    let pkh = PubKeyHash::from_secp256k1_blake160(*u8_slice!(
+        "0xb39bbc0b3673c7d36450bc14cfcdad2d559c6c64"
+    ));
+    let since = Since::epoch(false, 1000, 0, 1).unwrap();
+    let time_lock = TimeLock::new(&pkh, since);
+    let addr = time_lock.address(super::Network::Main).unwrap();
+    assert_eq!(addr.format(), AddressFormat::Full);
+    assert_eq!(addr.kind(), AddressKind::MultiSig);
+    let args = addr.to_script().into_parts().2;
+    let mut expected = vec![0x00, 0x00, 0x01, 0x01];
+    expected.extend_from_slice(pkh.as_slice());
+    assert_eq!(&args[..20], &crate::blake2b::blake160(&expected)[..]);
+    assert_eq!(&args[20..], &since.to_le_bytes()[..]);
+
+    let addr = super::Address::from_str(&addr.to_string()).unwrap();
+    assert_eq!(addr.since(), Some(since));
+    assert_eq!(addr.time_lock(&pkh), Some(time_lock.clone()));
+    assert_eq!(
+        time_lock.to_string(),
+        format!("{} after epoch 1000+0/1", pkh)
+    );
+    let other = PubKeyHash::from_secp256k1_blake160([0u8; 20]);
+    assert!(addr.time_lock(&other).is_none());
+    let no_since = super::AddressBuilder::default()
+        .code_hash_by_index(super::CodeHashIndex::Secp256k1MultiSig)
+        .args_multisig_config(time_lock.config(), None)
+        .build()
+        .unwrap();
+    assert!(no_since.time_lock(&pkh).is_none());
+    let network = super::Network::register(super::NetworkSpec::new("cktimelock", "tl")).unwrap();
+    assert!(time_lock.address(network).is_err());
+}
+
+#[test]
+fn test_multisig_validation() {
+    use super::{error::Error, MultiSigConfig};
+    let pkh1 = *u8_slice!("0xbd07d9f32bce34d27152a6a0391d324f79aab854");
+    let pkh2 = *u8_slice!("0x094ee28566dff02a012a66505822a2fd67d668fb");
+    let pkh3 = *u8_slice!("0x4643c241e59e81b7876527ebff23dfb24cf16482");
+    let build = |version: u8, first_n: u8, threshold: u8, contents: Vec<[u8; 20]>| {
+        super::AddressBuilder::default()
+            .code_hash_by_index(super::CodeHashIndex::Secp256k1MultiSig)
+            .args_multisig(version, first_n, threshold, contents, None)
+            .build()
+    };
+    assert!(build(0, 1, 2, vec![pkh1, pkh2, pkh3]).is_ok());
+    assert_matches!(
+        build(1, 0, 2, vec![pkh1, pkh2, pkh3]),
+        Err(Error::UnsupportedMultiSigVersion(1))
+    );
+    assert_matches!(
+        build(0, 0, 0, vec![pkh1, pkh2, pkh3]),
+        Err(Error::ZeroMultiSigThreshold)
+    );
+    assert_matches!(
+        build(0, 0, 4, vec![pkh1, pkh2, pkh3]),
+        Err(Error::MultiSigThresholdTooLarge(4, 3))
+    );
+    assert_matches!(
+        build(0, 3, 2, vec![pkh1, pkh2, pkh3]),
+        Err(Error::MultiSigFirstNTooLarge(3, 2))
+    );
+    assert_matches!(
+        build(0, 0, 2, vec![pkh1, pkh2, pkh1]),
+        Err(Error::DuplicateMultiSigKey(_))
+    );
+    let many = (0..256u16)
+        .map(|i| {
+            let mut pkh = [0u8; 20];
+            pkh[..2].copy_from_slice(&i.to_le_bytes());
+            pkh
+        })
+        .collect::<Vec<_>>();
+    assert_matches!(
+        build(0, 0, 1, many.clone()),
+        Err(Error::TooManyMultiSigKeys(256))
+    );
+    assert!(build(0, 0, 1, many[..255].to_vec()).is_ok());
+
+    let config = MultiSigConfig::canonical(0, 1, 2, vec![pkh3, pkh2, pkh1, pkh2]).unwrap();
+    assert_eq!(config.pubkey_hashes(), &[pkh3, pkh2, pkh1][..]);
+    assert!(config.is_canonical());
+    let config = MultiSigConfig::canonical(0, 0, 2, vec![pkh1, pkh2, pkh3]).unwrap();
+    assert_eq!(config.pubkey_hashes(), &[pkh2, pkh3, pkh1][..]);
+    assert!(!MultiSigConfig::new(0, 0, 2, vec![pkh1, pkh2, pkh3])
+        .unwrap()
+        .is_canonical());
+    assert!(MultiSigConfig::canonical(0, 0, 3, vec![pkh1, pkh2, pkh1]).is_err());
+}
+
+#[test]
+fn test_multisig_from_public_keys() {
+    use super::{AddressFormat, MultiSigConfig};
+    use crate::secp256k1::SecretKey;
+    let pubkeys = (1..=3)
+        .map(|seed| SecretKey::from_seed(seed).public_key())
+        .collect::<Vec<_>>();
+    let (addr, config) =
+        super::Address::multisig(super::Network::Test, 1, 2, &pubkeys, None).unwrap();
+    assert_eq!(addr.format(), AddressFormat::Full);
+    assert_eq!(config.first_n_required(), 1);
+    assert_eq!(config.threshold(), 2);
+    for (pubkey, pubkey_hash) in pubkeys.iter().zip(config.pubkey_hashes()) {
+        assert_eq!(pubkey.pkhash_blake160().as_slice(), &pubkey_hash[..]);
+    }
+    let serialized = config.serialize();
+    assert_eq!(&serialized[..4], &[0, 1, 2, 3][..]);
+    assert_eq!(MultiSigConfig::from_slice(&serialized).unwrap(), config);
+    assert!(config.matches(&addr));
+    assert_eq!(
+        addr.multisig_hash(),
+        Some(crate::blake2b::blake160(&serialized))
+    );
+    assert!(super::Address::multisig(super::Network::Test, 0, 4, &pubkeys, None).is_err());
+    let duplicate = vec![pubkeys[0], pubkeys[0]];
+    assert!(super::Address::multisig(super::Network::Test, 0, 1, &duplicate, None).is_err());
+}
+
+#[test]
+fn test_diagnose() {
+    let replace = |s: &str, positions: &[usize], c: char| {
+        s.chars()
+            .enumerate()
+            .map(|(i, x)| if positions.contains(&i) { c } else { x })
+            .collect::<String>()
+    };
+    let short = "ckb1qyqt8xaupvm8837nv3gtc9x0ekkj64vud3jqfwyw5v";
+    let full = "ckb1qzda0cr08m85hc8jlnfp3zer7xulejywt49kt2rr0vthywaa50xwsqdnnw7qkdnnclfkg59uzn8umt\
+                fd2kwxceqxwquc4";
+    let diagnosis = super::Address::diagnose(short);
+    assert!(diagnosis.is_valid());
+    assert!(diagnosis.candidates().is_empty());
+
+    for expected in &[short, full] {
+        let typo = replace(expected, &[20], 'q');
+        let diagnosis = super::Address::diagnose(&typo);
+        assert!(!diagnosis.is_valid());
+        assert_eq!(diagnosis.positions(), &[20][..]);
+        assert_eq!(diagnosis.candidates().len(), 1);
+        let candidate = &diagnosis.candidates()[0];
+        assert_eq!(&candidate.address().to_string(), expected);
+        assert_eq!(candidate.positions(), &[20][..]);
+
+        let typo = replace(expected, &[10, 30], 'l');
+        let diagnosis = super::Address::diagnose(&typo);
+        assert!(diagnosis
+            .candidates()
+            .iter()
+            .any(|c| &c.address().to_string() == expected && c.positions() == [10, 30]));
+
+        let typo = replace(expected, &[12], 'b');
+        let diagnosis = super::Address::diagnose(&typo);
+        assert_eq!(diagnosis.positions(), &[12][..]);
+        assert_eq!(diagnosis.candidates().len(), 1);
+        assert_eq!(&diagnosis.candidates()[0].address().to_string(), expected);
+    }
+
+    let typo = replace(short, &[10, 20, 30], 'l');
+    let diagnosis = super::Address::diagnose(&typo);
+    assert!(diagnosis
+        .candidates()
+        .iter()
+        .all(|c| c.address().to_string() != short));
+    let diagnosis = super::Address::diagnose("ckb1qyqt8xaupvm8837nv3gtc9x0ekkj64vud3jqfwy");
+    assert!(!diagnosis.is_valid());
+    let diagnosis = super::Address::diagnose("no separator");
+    assert!(diagnosis.candidates().is_empty());
+
+    let too_long = format!("{}{}", short, "b".repeat(super::ADDRESS_MAX_LENGTH));
+    let diagnosis = super::Address::diagnose(&too_long);
+    assert_matches!(
+        diagnosis.error(),
+        Some(super::error::Error::InvalidLength(len)) if *len == too_long.len()
+    );
+    assert!(diagnosis.positions().is_empty());
+    assert!(diagnosis.candidates().is_empty());
+}
+
+#[test]
+fn test_long_args() {
+    use super::{OmniAuth, OmniLockArgs, Since, ADDRESS_MAX_LENGTH};
+    let pkh = *u8_slice!("0xb39bbc0b3673c7d36450bc14cfcdad2d559c6c64");
+    let test = |addr: super::Address| {
+        let s = addr.to_string();
+        assert!(s.len() > 90);
+        let decoded = super::Address::from_str(&s).unwrap();
+        assert_eq!(decoded, addr);
+        assert_eq!(decoded.to_string(), s);
+        decoded
+    };
+
+    let config = super::MultiSigConfig::new(0, 0, 1, vec![pkh]).unwrap();
+    let since = Since::epoch(true, 10, 1, 2).unwrap();
+    let addr = test(config.address(super::Network::Main, Some(since)).unwrap());
+    assert_eq!(addr.since(), Some(since));
+
+    let args = OmniLockArgs::new(OmniAuth::new(super::OmniAuthFlag::Ethereum, pkh))
+        .with_admin_list([0x11; 32])
+        .with_anyone_can_pay(1, 2)
+        .with_time_lock(since)
+        .with_supply([0x22; 32]);
+    let addr = test(args.address(super::Network::Test).unwrap());
+    assert_eq!(addr.omnilock_args(), Some(args));
+
+    let build = |len: usize| {
+        super::AddressBuilder::default()
+            .code_hash_by_data(super::CodeHashType::Data1, [0x33; 32])
+            .args_simple(vec![0x44; len])
+            .build()
+    };
+    // 4 (hrp and separator) + (34 + len) * 8 / 5 (data) + 6 (checksum)
+    let addr = test(build(599).unwrap());
+    assert_eq!(addr.to_string().len(), ADDRESS_MAX_LENGTH);
+    assert_matches!(build(600), Err(super::error::Error::InvalidLength(1025)));
+    let too_long = format!("ckb1{}", "q".repeat(ADDRESS_MAX_LENGTH));
+    assert_matches!(
+        super::Address::from_str(&too_long),
+        Err(super::error::Error::InvalidLength(_))
+    );
+}
+
+#[test]
+fn test_build_over_length() {
+    let result = super::AddressBuilder::default()
+        .code_hash_by_data(super::CodeHashType::Type, [0x33; 32])
+        .args_simple(vec![0x44; 700])
+        .build();
+    assert_matches!(result, Err(super::error::Error::InvalidLength(1185)));
+    let script = crate::Script::new([0x33; 32], super::CodeHashType::Type, vec![0x44; 700]);
+    assert_matches!(
+        super::Address::from_script(script, super::Network::Main),
+        Err(super::error::Error::InvalidLength(1185))
+    );
+}
+
+#[test]
+fn test_verify_signature() {
+    let sk = crate::secp256k1::SecretKey::from_seed(1);
+    let msg = [0x5a; 32];
+    let sig = sk.sign_recoverable(&msg[..]).unwrap();
+    let pkh = sk.public_key().pkhash_blake160();
+    let addr = pkh.address(super::Network::Main).unwrap();
+    assert!(addr.verify_signature(&msg[..], &sig).is_ok());
+    let addr = super::AddressBuilder::default()
+        .network(super::Network::Test)
+        .code_hash_by_index(super::CodeHashIndex::AnyoneCanPay)
+        .args_simple([pkh.as_slice(), &[1u8][..]].concat())
+        .build()
+        .unwrap();
+    assert!(addr.verify_signature(&msg[..], &sig).is_ok());
+
+    let other = crate::secp256k1::SecretKey::from_seed(2)
+        .public_key()
+        .pkhash_blake160()
+        .address(super::Network::Main)
+        .unwrap();
+    assert_matches!(
+        other.verify_signature(&msg[..], &sig),
+        Err(super::error::Error::Secp256k1(_))
+    );
+    let addr = super::AddressBuilder::default()
+        .code_hash_by_index(super::CodeHashIndex::Secp256k1MultiSig)
+        .args_simple(pkh.as_slice().to_vec())
+        .build()
+        .unwrap();
+    assert_matches!(
+        addr.verify_signature(&msg[..], &sig),
+        Err(super::error::Error::NoPubKeyHash)
+    );
+}
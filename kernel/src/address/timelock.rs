@@ -0,0 +1,90 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+use property::Property;
+
+use super::{
+    error::Result, Address, MultiSigConfig, Network, Since, BLAKE160_SIZE, MULTISIG_VERSION,
+};
+use crate::{secp256k1::PublicKey, utilities, PubKeyHash};
+
+/// Time Lock
+///
+/// A single key which could only unlock after the since, as a 1-of-1 multisig lock with a since.
+#[derive(Property, Clone, PartialEq, Eq, Hash)]
+#[property(get(public), set(disable), mut(disable))]
+pub struct TimeLock {
+    pubkey_hash: [u8; BLAKE160_SIZE],
+    #[property(get(type = "copy"))]
+    since: Since,
+}
+
+impl fmt::Debug for TimeLock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TimeLock {{")?;
+        write!(
+            f,
+            " pubkey_hash: {}",
+            utilities::hex_string(&self.pubkey_hash[..])
+        )?;
+        write!(f, " , since: {}", self.since)?;
+        write!(f, " }}")
+    }
+}
+
+impl fmt::Display for TimeLock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} after {}",
+            utilities::hex_string(&self.pubkey_hash[..]),
+            self.since
+        )
+    }
+}
+
+impl TimeLock {
+    pub fn new(pkhash: &PubKeyHash, since: Since) -> Self {
+        let mut pubkey_hash = [0u8; BLAKE160_SIZE];
+        pubkey_hash.copy_from_slice(pkhash.as_slice());
+        Self { pubkey_hash, since }
+    }
+
+    pub fn from_public_key(pubkey: &PublicKey, since: Since) -> Self {
+        Self::new(&pubkey.pkhash_blake160(), since)
+    }
+
+    /// The 1-of-1 multisig config of the key.
+    pub fn config(&self) -> MultiSigConfig {
+        MultiSigConfig::new(MULTISIG_VERSION, 0, 1, vec![self.pubkey_hash])
+            .unwrap_or_else(|_| unreachable!("1-of-1 multisig config is valid"))
+    }
+
+    /// Creates the time-locked address in the full format on the network.
+    pub fn address(&self, network: Network) -> Result<Address> {
+        self.config().address(network, Some(self.since))
+    }
+
+    /// Checks if the address is the time-locked address of this key and since.
+    pub fn matches(&self, address: &Address) -> bool {
+        address.multisig_hash() == Some(self.config().hash()) && address.since() == Some(self.since)
+    }
+}
+
+impl Address {
+    /// Returns the unlock condition if the address is a time-locked address of the key.
+    ///
+    /// Only the hash of the multisig script is in the address, so the key is required.
+    pub fn time_lock(&self, pkhash: &PubKeyHash) -> Option<TimeLock> {
+        self.since()
+            .map(|since| TimeLock::new(pkhash, since))
+            .filter(|time_lock| time_lock.matches(self))
+    }
+}
@@ -0,0 +1,38 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use thiserror::Error;
+
+use super::ChildNumber;
+use crate::{address, secp256k1};
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("secp256k1 error: {0}")]
+    Secp256k1(#[from] secp256k1::Error),
+    #[error("address error: {0}")]
+    Address(#[from] address::error::Error),
+
+    #[error("invalid seed length: {0}")]
+    InvalidSeedLength(usize),
+    #[error("invalid child number: {0}")]
+    InvalidChildNumber(String),
+    #[error("invalid derivation path: {0}")]
+    InvalidPath(String),
+    #[error("can not derive the hardened child {0} from a public key")]
+    HardenedFromPublic(ChildNumber),
+    #[error("the max depth is reached")]
+    MaxDepth,
+
+    #[error("base58 error: {0}")]
+    Base58(String),
+    #[error("invalid extended key: {0}")]
+    InvalidExtendedKey(String),
+}
+
+pub type Result<T> = ::std::result::Result<T, Error>;
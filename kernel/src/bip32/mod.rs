@@ -0,0 +1,365 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! BIP-32 hierarchical deterministic keys.
+
+use std::{convert::TryInto, str};
+
+use hmac::{Hmac, Mac};
+use property::Property;
+use sha2::Sha512;
+
+pub mod error;
+use error::{Error, Result};
+
+mod path;
+pub use path::{ChildNumber, DerivationPath, HARDENED_BIT};
+
+use crate::{
+    address::{Address, Network},
+    secp256k1::{PublicKey, SecretKey},
+    sha256, utilities,
+};
+
+#[cfg(test)]
+mod tests;
+
+pub const BIP44_PURPOSE: u32 = 44;
+pub const CKB_COIN_TYPE: u32 = 309;
+
+const MASTER_KEY: &[u8] = b"Bitcoin seed";
+const SEED_MIN_SIZE: usize = 16;
+const SEED_MAX_SIZE: usize = 64;
+const EXTENDED_KEY_SIZE: usize = 78;
+const XPRV_VERSION: [u8; 4] = [0x04, 0x88, 0xad, 0xe4];
+const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xb2, 0x1e];
+
+/// Key Chain
+///
+/// The change level of BIP-44 paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyChain {
+    External = 0,
+    Change = 1,
+}
+
+/// Extended Private Key
+#[derive(Property, Clone, PartialEq, Eq)]
+#[property(get(public), set(disable), mut(disable))]
+pub struct ExtendedPrivKey {
+    #[property(get(type = "copy"))]
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    #[property(get(type = "copy"))]
+    child_number: ChildNumber,
+    chain_code: [u8; 32],
+    secret_key: SecretKey,
+}
+
+/// Extended Public Key
+#[derive(Property, Clone, Copy, PartialEq, Eq)]
+#[property(get(public), set(disable), mut(disable))]
+pub struct ExtendedPubKey {
+    #[property(get(type = "copy"))]
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    #[property(get(type = "copy"))]
+    child_number: ChildNumber,
+    chain_code: [u8; 32],
+    public_key: PublicKey,
+}
+
+fn hmac_sha512(key: &[u8], parts: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = Hmac::<Sha512>::new_varkey(key)
+        .unwrap_or_else(|_| unreachable!("hmac accepts keys of any size"));
+    for part in parts {
+        mac.input(part);
+    }
+    let output = mac.result().code();
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+    (left, right)
+}
+
+fn fingerprint(pk: &PublicKey) -> [u8; 4] {
+    let mut fingerprint = [0u8; 4];
+    fingerprint.copy_from_slice(&sha256::hash160(&pk.serialize()[..])[..4]);
+    fingerprint
+}
+
+fn serialize(
+    version: [u8; 4],
+    depth: u8,
+    parent_fingerprint: &[u8; 4],
+    child_number: ChildNumber,
+    chain_code: &[u8; 32],
+    key: &[u8; 33],
+) -> String {
+    let mut data = Vec::with_capacity(EXTENDED_KEY_SIZE);
+    data.extend_from_slice(&version[..]);
+    data.push(depth);
+    data.extend_from_slice(&parent_fingerprint[..]);
+    data.extend_from_slice(&child_number.value().to_be_bytes()[..]);
+    data.extend_from_slice(&chain_code[..]);
+    data.extend_from_slice(&key[..]);
+    bs58::encode(data).with_check().into_string()
+}
+
+#[allow(clippy::type_complexity)]
+fn deserialize(
+    s: &str,
+    version: [u8; 4],
+) -> Result<(u8, [u8; 4], ChildNumber, [u8; 32], [u8; 33])> {
+    let data = bs58::decode(s)
+        .with_check(None)
+        .into_vec()
+        .map_err(|err| Error::Base58(err.to_string()))?;
+    if data.len() != EXTENDED_KEY_SIZE {
+        return Err(Error::InvalidExtendedKey(format!(
+            "invalid length {}",
+            data.len()
+        )));
+    }
+    if data[..4] != version[..] {
+        return Err(Error::InvalidExtendedKey(format!(
+            "unknown version {}",
+            utilities::hex_string(&data[..4])
+        )));
+    }
+    let depth = data[4];
+    let parent_fingerprint: [u8; 4] = data[5..9].try_into().unwrap();
+    let child_number = ChildNumber::from_value(u32::from_be_bytes(data[9..13].try_into().unwrap()));
+    let chain_code: [u8; 32] = data[13..45].try_into().unwrap();
+    let mut key = [0u8; 33];
+    key.copy_from_slice(&data[45..]);
+    if depth == 0 && (parent_fingerprint != [0u8; 4] || child_number.value() != 0) {
+        return Err(Error::InvalidExtendedKey(
+            "non-zero parent of the master key".to_owned(),
+        ));
+    }
+    Ok((depth, parent_fingerprint, child_number, chain_code, key))
+}
+
+#[cfg(feature = "insecure")]
+impl ::std::fmt::Debug for ExtendedPrivKey {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "{}", self.to_base58())
+    }
+}
+
+#[cfg(feature = "insecure")]
+impl ::std::fmt::Display for ExtendedPrivKey {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "{}", self.to_base58())
+    }
+}
+
+#[cfg(not(feature = "insecure"))]
+impl_std_fmt_masked!(ExtendedPrivKey);
+
+impl str::FromStr for ExtendedPrivKey {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let (depth, parent_fingerprint, child_number, chain_code, key) =
+            deserialize(s, XPRV_VERSION)?;
+        if key[0] != 0 {
+            return Err(Error::InvalidExtendedKey(
+                "invalid private key prefix".to_owned(),
+            ));
+        }
+        let secret_key = SecretKey::from_slice(&key[1..])?;
+        Ok(Self {
+            depth,
+            parent_fingerprint,
+            child_number,
+            chain_code,
+            secret_key,
+        })
+    }
+}
+
+impl ExtendedPrivKey {
+    /// Creates the master key from a seed, which should be 16 to 64 bytes.
+    pub fn new_master(seed: &[u8]) -> Result<Self> {
+        if seed.len() < SEED_MIN_SIZE || seed.len() > SEED_MAX_SIZE {
+            return Err(Error::InvalidSeedLength(seed.len()));
+        }
+        let (key, chain_code) = hmac_sha512(MASTER_KEY, &[seed]);
+        let secret_key = SecretKey::from_slice(&key[..])?;
+        Ok(Self {
+            depth: 0,
+            parent_fingerprint: [0u8; 4],
+            child_number: ChildNumber::from_value(0),
+            chain_code,
+            secret_key,
+        })
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.secret_key.public_key()
+    }
+
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint(&self.public_key())
+    }
+
+    pub fn to_extended_public_key(&self) -> ExtendedPubKey {
+        ExtendedPubKey {
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+            chain_code: self.chain_code,
+            public_key: self.public_key(),
+        }
+    }
+
+    pub fn derive_child(&self, child: ChildNumber) -> Result<Self> {
+        let depth = self.depth.checked_add(1).ok_or(Error::MaxDepth)?;
+        let index = child.value().to_be_bytes();
+        let (tweak, chain_code) = if child.is_hardened() {
+            hmac_sha512(&self.chain_code, &[&[0u8], &self.secret_key[..], &index])
+        } else {
+            hmac_sha512(
+                &self.chain_code,
+                &[&self.public_key().serialize()[..], &index],
+            )
+        };
+        let secret_key = self.secret_key.add_tweak(&tweak[..])?;
+        Ok(Self {
+            depth,
+            parent_fingerprint: self.fingerprint(),
+            child_number: child,
+            chain_code,
+            secret_key,
+        })
+    }
+
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self> {
+        path.as_ref()
+            .iter()
+            .try_fold(self.clone(), |key, child| key.derive_child(*child))
+    }
+
+    /// Derives the CKB account key `m/44'/309'/account'` from the master key.
+    pub fn ckb_account(&self, account: u32) -> Result<Self> {
+        self.derive_path(&DerivationPath::ckb_account(account)?)
+    }
+
+    /// Derives the nth key in the key chain of this account key.
+    pub fn derive_key(&self, chain: KeyChain, index: u32) -> Result<Self> {
+        self.derive_child(ChildNumber::normal(chain as u32)?)?
+            .derive_child(ChildNumber::normal(index)?)
+    }
+
+    /// Derives the address of the nth key in the key chain of this account key.
+    pub fn derive_address(&self, chain: KeyChain, index: u32, network: Network) -> Result<Address> {
+        self.to_extended_public_key()
+            .derive_address(chain, index, network)
+    }
+
+    /// Encodes the key as `xprv`.
+    ///
+    /// The secret key is included, do not show it to anyone.
+    pub fn to_base58(&self) -> String {
+        let mut key = [0u8; 33];
+        key[1..].copy_from_slice(&self.secret_key[..]);
+        serialize(
+            XPRV_VERSION,
+            self.depth,
+            &self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &key,
+        )
+    }
+}
+
+impl ::std::fmt::Debug for ExtendedPubKey {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl ::std::fmt::Display for ExtendedPubKey {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let key = self.public_key.serialize();
+        let s = serialize(
+            XPUB_VERSION,
+            self.depth,
+            &self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &key,
+        );
+        write!(f, "{}", s)
+    }
+}
+
+impl str::FromStr for ExtendedPubKey {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let (depth, parent_fingerprint, child_number, chain_code, key) =
+            deserialize(s, XPUB_VERSION)?;
+        let public_key = PublicKey::from_slice(&key[..])?;
+        Ok(Self {
+            depth,
+            parent_fingerprint,
+            child_number,
+            chain_code,
+            public_key,
+        })
+    }
+}
+
+impl ExtendedPubKey {
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint(&self.public_key)
+    }
+
+    /// Derives a normal child, the hardened children could not be derived from a public key.
+    pub fn derive_child(&self, child: ChildNumber) -> Result<Self> {
+        if child.is_hardened() {
+            return Err(Error::HardenedFromPublic(child));
+        }
+        let depth = self.depth.checked_add(1).ok_or(Error::MaxDepth)?;
+        let index = child.value().to_be_bytes();
+        let (tweak, chain_code) = hmac_sha512(
+            &self.chain_code,
+            &[&self.public_key.serialize()[..], &index],
+        );
+        let public_key = self.public_key.add_exp_tweak(&tweak[..])?;
+        Ok(Self {
+            depth,
+            parent_fingerprint: self.fingerprint(),
+            child_number: child,
+            chain_code,
+            public_key,
+        })
+    }
+
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self> {
+        path.as_ref()
+            .iter()
+            .try_fold(*self, |key, child| key.derive_child(*child))
+    }
+
+    /// Derives the address of the nth key in the key chain of this account key.
+    ///
+    /// The key is at `chain/index` under the account key, the address is in the short format.
+    pub fn derive_address(&self, chain: KeyChain, index: u32, network: Network) -> Result<Address> {
+        let key = self
+            .derive_child(ChildNumber::normal(chain as u32)?)?
+            .derive_child(ChildNumber::normal(index)?)?;
+        key.public_key
+            .pkhash_blake160()
+            .address(network)
+            .map_err(Into::into)
+    }
+}
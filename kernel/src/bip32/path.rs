@@ -0,0 +1,143 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::{fmt, str};
+
+use super::error::{Error, Result};
+
+/// The flag of the hardened child numbers.
+pub const HARDENED_BIT: u32 = 1 << 31;
+
+/// Child Number
+///
+/// The indexes with the hardened bit are hardened, the others are normal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ChildNumber(u32);
+
+/// Derivation Path
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct DerivationPath(Vec<ChildNumber>);
+
+impl ChildNumber {
+    pub fn normal(index: u32) -> Result<Self> {
+        if index & HARDENED_BIT == 0 {
+            Ok(Self(index))
+        } else {
+            Err(Error::InvalidChildNumber(index.to_string()))
+        }
+    }
+
+    pub fn hardened(index: u32) -> Result<Self> {
+        Self::normal(index).map(|Self(index)| Self(index | HARDENED_BIT))
+    }
+
+    pub fn from_value(value: u32) -> Self {
+        Self(value)
+    }
+
+    /// The value with the hardened bit.
+    pub fn value(self) -> u32 {
+        self.0
+    }
+
+    /// The index without the hardened bit.
+    pub fn index(self) -> u32 {
+        self.0 & !HARDENED_BIT
+    }
+
+    pub fn is_hardened(self) -> bool {
+        self.0 & HARDENED_BIT != 0
+    }
+}
+
+impl fmt::Display for ChildNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_hardened() {
+            write!(f, "{}'", self.index())
+        } else {
+            write!(f, "{}", self.index())
+        }
+    }
+}
+
+impl str::FromStr for ChildNumber {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let (index, hardened) = if s.ends_with(&['\'', 'h', 'H'][..]) {
+            (&s[..s.len() - 1], true)
+        } else {
+            (s, false)
+        };
+        if index.is_empty() || !index.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(Error::InvalidChildNumber(s.to_owned()));
+        }
+        let index = index
+            .parse::<u32>()
+            .map_err(|_| Error::InvalidChildNumber(s.to_owned()))?;
+        if hardened {
+            Self::hardened(index)
+        } else {
+            Self::normal(index)
+        }
+        .map_err(|_| Error::InvalidChildNumber(s.to_owned()))
+    }
+}
+
+impl From<Vec<ChildNumber>> for DerivationPath {
+    fn from(children: Vec<ChildNumber>) -> Self {
+        Self(children)
+    }
+}
+
+impl AsRef<[ChildNumber]> for DerivationPath {
+    fn as_ref(&self) -> &[ChildNumber] {
+        &self.0[..]
+    }
+}
+
+impl DerivationPath {
+    /// The path of a CKB account: `m/44'/309'/account'`.
+    pub fn ckb_account(account: u32) -> Result<Self> {
+        Ok(Self(vec![
+            ChildNumber::hardened(super::BIP44_PURPOSE)?,
+            ChildNumber::hardened(super::CKB_COIN_TYPE)?,
+            ChildNumber::hardened(account)?,
+        ]))
+    }
+
+    /// Appends a child number to the path.
+    pub fn child(mut self, child: ChildNumber) -> Self {
+        self.0.push(child);
+        self
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "m")?;
+        for child in &self.0 {
+            write!(f, "/{}", child)?;
+        }
+        Ok(())
+    }
+}
+
+impl str::FromStr for DerivationPath {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = s.split('/');
+        if parts.next() != Some("m") {
+            return Err(Error::InvalidPath(s.to_owned()));
+        }
+        parts
+            .map(|part| part.parse())
+            .collect::<Result<Vec<_>>>()
+            .map(Self)
+            .map_err(|_| Error::InvalidPath(s.to_owned()))
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use slices::u8_slice;
+use std::str::FromStr;
+
+use super::{ChildNumber, DerivationPath, ExtendedPrivKey, ExtendedPubKey, KeyChain};
+use crate::address::{Address, Network};
+
+#[test]
+fn test_vector_1() {
+    let seed = u8_slice!("0x000102030405060708090a0b0c0d0e0f");
+    let master = ExtendedPrivKey::new_master(&seed[..]).unwrap();
+    let test = |path: &str, xprv: &str, xpub: &str| {
+        let path = DerivationPath::from_str(path).unwrap();
+        let key = master.derive_path(&path).unwrap();
+        assert_eq!(key.to_base58(), xprv);
+        assert_eq!(key.to_extended_public_key().to_string(), xpub);
+        let decoded = ExtendedPrivKey::from_str(xprv).unwrap();
+        assert_eq!(decoded, key);
+        let decoded = ExtendedPubKey::from_str(xpub).unwrap();
+        assert_eq!(decoded, key.to_extended_public_key());
+    };
+    test(
+        "m",
+        "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRN\
+         NU3TGtRBeJgk33yuGBxrMPHi",
+        "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD\
+         265TMg7usUDFdp6W1EGMcet8",
+    );
+    test(
+        "m/0'",
+        "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11e\
+         ZG7XnxHrnYeSvkzY7d2bhkJ7",
+        "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgw\
+         Q9xv5ski8PX9rL2dZXvgGDnw",
+    );
+    test(
+        "m/0H/1",
+        "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MS\
+         Y3H2EU4pWcQDnRnrVA1xe8fs",
+        "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527\
+         Hqck2AxYysAA7xmALppuCkwQ",
+    );
+
+    let parent = master
+        .derive_path(&DerivationPath::from_str("m/0'").unwrap())
+        .unwrap();
+    let child = ChildNumber::normal(1).unwrap();
+    assert_eq!(
+        parent.to_extended_public_key().derive_child(child).unwrap(),
+        parent.derive_child(child).unwrap().to_extended_public_key()
+    );
+    let hardened = ChildNumber::hardened(1).unwrap();
+    assert!(parent
+        .to_extended_public_key()
+        .derive_child(hardened)
+        .is_err());
+}
+
+#[test]
+fn test_path() {
+    let path = DerivationPath::from_str("m/44'/309'/0'/0/1").unwrap();
+    assert_eq!(path.to_string(), "m/44'/309'/0'/0/1");
+    assert_eq!(DerivationPath::from_str("m/44h/309H/0'/0/1").unwrap(), path);
+    let expected = DerivationPath::ckb_account(0)
+        .unwrap()
+        .child(ChildNumber::normal(0).unwrap())
+        .child(ChildNumber::normal(1).unwrap());
+    assert_eq!(path, expected);
+    assert_eq!(DerivationPath::from_str("m").unwrap().as_ref().len(), 0);
+    for invalid in &[
+        "",
+        "44'/0",
+        "m/",
+        "m/a",
+        "m/-1",
+        "m/2147483648",
+        "m/1''",
+        "m/+1",
+    ] {
+        assert!(DerivationPath::from_str(invalid).is_err(), "{}", invalid);
+    }
+    let child = ChildNumber::from_str("2147483647'").unwrap();
+    assert!(child.is_hardened());
+    assert_eq!(child.index(), 2_147_483_647);
+    assert_eq!(child.value(), std::u32::MAX);
+}
+
+#[test]
+fn test_ckb_addresses() {
+    let seed = u8_slice!("0x000102030405060708090a0b0c0d0e0f");
+    let master = ExtendedPrivKey::new_master(&seed[..]).unwrap();
+    let account = master.ckb_account(0).unwrap();
+    let xpub = account.to_extended_public_key();
+    for (chain, i) in &[(KeyChain::External, 0), (KeyChain::Change, 5)] {
+        let path = format!("m/44'/309'/0'/{}/{}", *chain as u32, i);
+        let key = master
+            .derive_path(&DerivationPath::from_str(&path).unwrap())
+            .unwrap();
+        let expected = key
+            .public_key()
+            .pkhash_blake160()
+            .address(Network::Test)
+            .unwrap();
+        let actual = xpub.derive_address(*chain, *i, Network::Test).unwrap();
+        assert_eq!(actual, expected);
+        assert_eq!(
+            account.derive_address(*chain, *i, Network::Test).unwrap(),
+            expected
+        );
+        assert!(Address::from_str(&actual.to_string()).is_ok());
+    }
+    assert_ne!(
+        xpub.derive_address(KeyChain::External, 0, Network::Main)
+            .unwrap(),
+        xpub.derive_address(KeyChain::Change, 0, Network::Main)
+            .unwrap()
+    );
+    assert!(xpub
+        .derive_address(KeyChain::External, 1 << 31, Network::Main)
+        .is_err());
+    assert!(ExtendedPrivKey::new_master(&[0u8; 15]).is_err());
+    assert!(ExtendedPrivKey::new_master(&[0u8; 65]).is_err());
+    assert!(ExtendedPrivKey::from_str(&xpub.to_string()).is_err());
+    assert!(ExtendedPubKey::from_str(&account.to_base58()).is_err());
+}
@@ -0,0 +1,28 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use thiserror::Error;
+
+use crate::bip32;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("bip32 error: {0}")]
+    Bip32(#[from] bip32::error::Error),
+
+    #[error("invalid entropy size: {0} bits")]
+    InvalidEntropySize(usize),
+    #[error("invalid word count: {0}")]
+    InvalidWordCount(usize),
+    #[error("unknown word: {0}")]
+    UnknownWord(String),
+    #[error("invalid checksum")]
+    InvalidChecksum,
+}
+
+pub type Result<T> = ::std::result::Result<T, Error>;
@@ -0,0 +1,216 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! BIP-39 mnemonic codes, with the English wordlist.
+
+use std::{ops::Drop, ptr, str, sync::atomic};
+
+use lazy_static::lazy_static;
+use pbkdf2::pbkdf2;
+use rand::{thread_rng, Rng};
+use unicode_normalization::UnicodeNormalization;
+
+pub mod error;
+use error::{Error, Result};
+
+use crate::{
+    bip32::{ExtendedPrivKey, KeyChain},
+    secp256k1::SecretKey,
+    sha256,
+};
+
+#[cfg(test)]
+mod tests;
+
+const PBKDF2_ROUNDS: usize = 2048;
+const SEED_SIZE: usize = 64;
+const BITS_PER_WORD: usize = 11;
+
+lazy_static! {
+    static ref ENGLISH: Vec<&'static str> = include_str!("english.txt").lines().collect();
+}
+
+/// Mnemonic
+///
+/// The entropy should be 128, 160, 192, 224 or 256 bits, which is 12 to 24 words.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mnemonic {
+    entropy: Vec<u8>,
+    phrase: String,
+}
+
+#[cfg(feature = "insecure")]
+impl ::std::fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "Mnemonic({})", self.phrase)
+    }
+}
+
+#[cfg(feature = "insecure")]
+impl ::std::fmt::Display for Mnemonic {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "{}", self.phrase)
+    }
+}
+
+#[cfg(not(feature = "insecure"))]
+impl_std_fmt_masked!(Mnemonic);
+
+fn check_entropy_size(bits: usize) -> Result<()> {
+    if !(128..=256).contains(&bits) || bits % 32 != 0 {
+        Err(Error::InvalidEntropySize(bits))
+    } else {
+        Ok(())
+    }
+}
+
+fn zeroize(bytes: &mut [u8]) {
+    let dst = bytes.as_mut_ptr();
+    for of in 0..bytes.len() {
+        unsafe {
+            ptr::write_volatile(dst.add(of), 0);
+        }
+        atomic::compiler_fence(atomic::Ordering::SeqCst);
+    }
+}
+
+impl Drop for Mnemonic {
+    fn drop(&mut self) {
+        zeroize(&mut self.entropy);
+        // Safe: zeros are valid UTF-8.
+        zeroize(unsafe { self.phrase.as_bytes_mut() });
+    }
+}
+
+impl str::FromStr for Mnemonic {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_phrase(s)
+    }
+}
+
+impl Mnemonic {
+    /// Generates a mnemonic with the entropy from the random number generator.
+    pub fn new<R: Rng + ?Sized>(rng: &mut R, entropy_bits: usize) -> Result<Self> {
+        check_entropy_size(entropy_bits)?;
+        let mut entropy = vec![0u8; entropy_bits / 8];
+        rng.fill(&mut entropy[..]);
+        let mnemonic = Self::from_entropy(&entropy);
+        zeroize(&mut entropy);
+        mnemonic
+    }
+
+    /// Generates a random mnemonic.
+    pub fn random(entropy_bits: usize) -> Result<Self> {
+        let mut rng = thread_rng();
+        Self::new(&mut rng, entropy_bits)
+    }
+
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self> {
+        let bits = entropy.len() * 8;
+        check_entropy_size(bits)?;
+        let checksum = sha256::sha256(entropy)[0];
+        let checksum_bits = bits / 32;
+        let word_count = (bits + checksum_bits) / BITS_PER_WORD;
+        let bit = |i: usize| {
+            let byte = if i < bits { entropy[i / 8] } else { checksum };
+            (byte >> (7 - i % 8)) & 1
+        };
+        let words = (0..word_count)
+            .map(|w| {
+                let index = (0..BITS_PER_WORD).fold(0usize, |index, i| {
+                    (index << 1) | usize::from(bit(w * BITS_PER_WORD + i))
+                });
+                ENGLISH[index]
+            })
+            .collect::<Vec<_>>();
+        Ok(Self {
+            entropy: entropy.to_owned(),
+            phrase: words.join(" "),
+        })
+    }
+
+    /// Parses a mnemonic phrase and validates its checksum.
+    pub fn from_phrase(phrase: &str) -> Result<Self> {
+        let phrase = phrase.nfkd().collect::<String>();
+        let words = phrase.split_whitespace().collect::<Vec<_>>();
+        let word_count = words.len();
+        if !(12..=24).contains(&word_count) || word_count % 3 != 0 {
+            return Err(Error::InvalidWordCount(word_count));
+        }
+        let mut bits = Vec::with_capacity(word_count * BITS_PER_WORD);
+        for word in &words {
+            let index = ENGLISH
+                .binary_search(word)
+                .map_err(|_| Error::UnknownWord((*word).to_owned()))?;
+            for i in (0..BITS_PER_WORD).rev() {
+                bits.push((index >> i) & 1 == 1);
+            }
+        }
+        let entropy_bits = word_count * BITS_PER_WORD * 32 / 33;
+        let entropy = bits[..entropy_bits]
+            .chunks(8)
+            .map(|byte| byte.iter().fold(0u8, |b, bit| (b << 1) | u8::from(*bit)))
+            .collect::<Vec<_>>();
+        let mnemonic = Self::from_entropy(&entropy)?;
+        if mnemonic.phrase.split(' ').eq(words.iter().copied()) {
+            Ok(mnemonic)
+        } else {
+            Err(Error::InvalidChecksum)
+        }
+    }
+
+    pub fn entropy(&self) -> &[u8] {
+        &self.entropy[..]
+    }
+
+    /// The mnemonic phrase, which should be kept secret.
+    pub fn phrase(&self) -> &str {
+        &self.phrase
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.phrase.split(' ').count()
+    }
+
+    /// The seed of the mnemonic with an optional passphrase, an empty string for no passphrase.
+    pub fn to_seed(&self, passphrase: &str) -> [u8; SEED_SIZE] {
+        let mut salt = String::from("mnemonic");
+        salt.extend(passphrase.nfkd());
+        let mut seed = [0u8; SEED_SIZE];
+        pbkdf2::<hmac::Hmac<sha2::Sha512>>(
+            self.phrase.as_bytes(),
+            salt.as_bytes(),
+            PBKDF2_ROUNDS,
+            &mut seed,
+        );
+        seed
+    }
+
+    pub fn to_master_key(&self, passphrase: &str) -> Result<ExtendedPrivKey> {
+        let mut seed = self.to_seed(passphrase);
+        let master = ExtendedPrivKey::new_master(&seed[..]);
+        zeroize(&mut seed[..]);
+        master.map_err(Into::into)
+    }
+
+    /// Derives the secret key at `m/44'/309'/account'/chain/index`, as Neuron and ckb-cli do.
+    pub fn derive_secret_key(
+        &self,
+        passphrase: &str,
+        account: u32,
+        chain: KeyChain,
+        index: u32,
+    ) -> Result<SecretKey> {
+        self.to_master_key(passphrase)?
+            .ckb_account(account)?
+            .derive_key(chain, index)
+            .map(|key| key.secret_key().clone())
+            .map_err(Into::into)
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use slices::u8_slice;
+use std::str::FromStr;
+
+use super::{error::Error, Mnemonic};
+use crate::{address::Network, bip32::KeyChain};
+
+#[test]
+fn test_vectors() {
+    let test = |entropy: &[u8], phrase: &str| {
+        let mnemonic = Mnemonic::from_entropy(entropy).unwrap();
+        assert_eq!(mnemonic.phrase(), phrase);
+        let decoded = Mnemonic::from_phrase(phrase).unwrap();
+        assert_eq!(decoded.entropy(), entropy);
+    };
+    test(
+        &u8_slice!("0x00000000000000000000000000000000")[..],
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+         about",
+    );
+    test(
+        &u8_slice!("0x7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f")[..],
+        "legal winner thank year wave sausage worth useful legal winner thank yellow",
+    );
+    test(
+        &u8_slice!("0x80808080808080808080808080808080")[..],
+        "letter advice cage absurd amount doctor acoustic avoid letter advice cage above",
+    );
+    test(
+        &u8_slice!("0xffffffffffffffffffffffffffffffff")[..],
+        "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+    );
+    test(
+        &u8_slice!("0x8080808080808080808080808080808080808080808080808080808080808080")[..],
+        "letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount \
+         doctor acoustic avoid letter advice cage absurd amount doctor acoustic bless",
+    );
+
+    let mnemonic = Mnemonic::from_entropy(&[0u8; 16]).unwrap();
+    assert_eq!(
+        &mnemonic.to_seed("TREZOR")[..],
+        &u8_slice!(
+            "0xc55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e5349553\
+             1f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        )[..]
+    );
+    assert_eq!(
+        mnemonic.to_master_key("TREZOR").unwrap().to_base58(),
+        "xprv9s21ZrQH143K3h3fDYiay8mocZ3afhfULfb5GX8kCBdno77K4HiA15Tg23wpbeF1pLfs1c5SPmYHrEpTuuRhxMwv\
+         KDwqdKiGJS9XFKzUsAF"
+    );
+}
+
+#[test]
+fn test_invalid() {
+    assert_matches!(
+        Mnemonic::from_entropy(&[0u8; 15]),
+        Err(Error::InvalidEntropySize(120))
+    );
+    assert_matches!(
+        Mnemonic::from_phrase("abandon abandon abandon"),
+        Err(Error::InvalidWordCount(3))
+    );
+    assert_matches!(
+        Mnemonic::from_phrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+             abandon abandon"
+        ),
+        Err(Error::InvalidChecksum)
+    );
+    assert_matches!(
+        Mnemonic::from_phrase(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+             abandon abandonn"
+        ),
+        Err(Error::UnknownWord(_))
+    );
+}
+
+#[test]
+fn test_random_and_derive() {
+    for bits in (128..=256).step_by(32) {
+        let mnemonic = Mnemonic::random(bits).unwrap();
+        assert_eq!(mnemonic.word_count(), bits / 32 * 3);
+        let decoded = Mnemonic::from_str(mnemonic.phrase()).unwrap();
+        assert_eq!(decoded, mnemonic);
+    }
+
+    let mnemonic = Mnemonic::random(128).unwrap();
+    let account = mnemonic.to_master_key("").unwrap().ckb_account(0).unwrap();
+    let secret_key = mnemonic
+        .derive_secret_key("", 0, KeyChain::External, 3)
+        .unwrap();
+    let expected = account
+        .derive_address(KeyChain::External, 3, Network::Main)
+        .unwrap();
+    let address = secret_key
+        .public_key()
+        .pkhash_blake160()
+        .address(Network::Main)
+        .unwrap();
+    assert_eq!(address, expected);
+}
+
+#[test]
+fn test_ckb_derivation_vector() {
+    let mnemonic = Mnemonic::from_phrase(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+         about",
+    )
+    .unwrap();
+    // m/44'/309'/0'/0/0
+    let secret_key = mnemonic
+        .derive_secret_key("", 0, KeyChain::External, 0)
+        .unwrap();
+    assert_eq!(
+        &secret_key[..],
+        &u8_slice!("0xb217d9a18ff657c99872cc11a2fa2aa3e970cef8c6faa7d6e424bf057cb3707b")[..]
+    );
+    let account = mnemonic.to_master_key("").unwrap().ckb_account(0).unwrap();
+    for (network, expected) in &[
+        (
+            Network::Main,
+            "ckb1qyqpjmmvrusl0klsm7q52wdcgqzelt96lsjqvr9fh0",
+        ),
+        (
+            Network::Test,
+            "ckt1qyqpjmmvrusl0klsm7q52wdcgqzelt96lsjq3xmkmn",
+        ),
+    ] {
+        let address = account
+            .derive_address(KeyChain::External, 0, *network)
+            .unwrap();
+        assert_eq!(&address.to_string(), expected);
+    }
+}
@@ -0,0 +1,25 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use tiny_keccak::{Hasher, Keccak};
+
+pub fn keccak_256<T: AsRef<[u8]>>(s: T) -> [u8; 32] {
+    let mut result = [0u8; 32];
+    let mut keccak = Keccak::v256();
+    keccak.update(s.as_ref());
+    keccak.finalize(&mut result);
+    result
+}
+
+/// The last 20 bytes of the keccak256 hash, as the Ethereum address.
+pub fn keccak160<T: AsRef<[u8]>>(s: T) -> [u8; 20] {
+    let mut result = [0u8; 20];
+    let hash = keccak_256(s);
+    result.copy_from_slice(&hash[12..]);
+    result
+}
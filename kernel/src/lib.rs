@@ -13,10 +13,19 @@ mod pkhash;
 pub use pkhash::PubKeyHash;
 
 pub mod address;
+pub mod script;
+pub use script::Script;
 
+pub mod bip32;
+pub mod bip39;
 pub mod blake2b;
+pub mod keccak;
 pub mod secp256k1;
 
+#[cfg(feature = "serde")]
+mod serde_impls;
+pub mod sha256;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HashAlgo {
     Blake2b256,
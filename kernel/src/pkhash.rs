@@ -7,8 +7,8 @@
 // except according to those terms.
 
 use crate::{
-    address::{Address, AddressBuilder, CodeHashIndex, Network},
-    utilities,
+    address::{error::Result, Address, AddressBuilder, CodeHashIndex, Network},
+    secp256k1, utilities,
 };
 
 pub enum PubKeyHash {
@@ -36,19 +36,31 @@ impl PubKeyHash {
         Self::Secp256k1Blake160(data)
     }
 
-    pub fn address(&self, is_mainnet: bool) -> Address {
-        let network = if is_mainnet {
-            Network::Main
-        } else {
-            Network::Test
-        };
+    pub fn address(&self, network: Network) -> Result<Address> {
         match *self {
             Self::Secp256k1Blake160(ref data) => AddressBuilder::default()
                 .network(network)
                 .code_hash_by_index(CodeHashIndex::Secp256k1Blake160)
                 .args_simple(data.to_vec())
-                .build()
-                .unwrap(),
+                .build(),
+        }
+    }
+
+    /// Checks if the signature of the message was signed by the key behind this hash.
+    pub fn verify(
+        &self,
+        input: &[u8],
+        sig: &secp256k1::Signature,
+    ) -> ::std::result::Result<(), secp256k1::Error> {
+        match *self {
+            Self::Secp256k1Blake160(ref data) => {
+                let pk = sig.recover(input)?;
+                if pk.pkhash_blake160().as_slice() == &data[..] {
+                    Ok(())
+                } else {
+                    Err(secp256k1::Error::IncorrectSignature)
+                }
+            }
         }
     }
 
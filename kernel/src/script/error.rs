@@ -0,0 +1,19 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("molecule error: {0}")]
+    Molecule(String),
+    #[error("unknown hash type: {0}")]
+    UnknownHashType(u8),
+}
+
+pub type Result<T> = ::std::result::Result<T, Error>;
@@ -0,0 +1,344 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+use lazy_static::lazy_static;
+use property::Property;
+
+use crate::{
+    address::{CodeHash, CodeHashType, CODE_HASH_SIZE},
+    utilities,
+};
+
+lazy_static! {
+    static ref MAINNET: Vec<KnownScript> = vec![
+        KnownScript::new(
+            SystemScript::Secp256k1Blake160,
+            CodeHashType::Type,
+            h256("9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8"),
+            vec![CellDep::new(
+                h256("71a7ba8fc96349fea0ed3a5c47992e3b4084b031a42264a018e0072e8172e46c"),
+                0,
+                DepType::DepGroup,
+            )],
+        ),
+        KnownScript::new(
+            SystemScript::Secp256k1MultiSig,
+            CodeHashType::Type,
+            h256("5c5069eb0857efc65e1bca0c07df34c31663b3622fd3876c876320fc9634e2a8"),
+            vec![CellDep::new(
+                h256("71a7ba8fc96349fea0ed3a5c47992e3b4084b031a42264a018e0072e8172e46c"),
+                1,
+                DepType::DepGroup,
+            )],
+        ),
+        KnownScript::new(
+            SystemScript::AnyoneCanPay,
+            CodeHashType::Type,
+            h256("d369597ff47f29fbc0d47d2e3775370d1250b85140c670e4718af712983a2354"),
+            vec![CellDep::new(
+                h256("4153a2014952d7cac45f285ce9a7c5c0c0e1b21f2d378b82ac1433cb11c25c4d"),
+                0,
+                DepType::DepGroup,
+            )],
+        ),
+        KnownScript::new(
+            SystemScript::Cheque,
+            CodeHashType::Type,
+            h256("e4d4ecc6e5f9a059bf2f7a82cca292083aebc0c421566a52484fe2ec51a9fb0c"),
+            vec![CellDep::new(
+                h256("04632cc459459cf5c9d384b43dee3e36f542a464bdd4127be7d6618ac6f8d268"),
+                0,
+                DepType::DepGroup,
+            )],
+        ),
+        KnownScript::new(
+            SystemScript::NervosDao,
+            CodeHashType::Type,
+            h256("82d76d1b75fe2fd9a27dfbaa65a039221a380d76c926f378d3f81cf3e7e13f2e"),
+            vec![CellDep::new(
+                h256("e2fb199810d49a4d8beec56718ba2593b665db9d52299a0f9e6e75416d73ff5c"),
+                2,
+                DepType::Code,
+            )],
+        ),
+        KnownScript::new(
+            SystemScript::Sudt,
+            CodeHashType::Type,
+            h256("5e7a36a77e68eecc013dfa2fe6a23f3b6c344b04005808694ae6dd45eea4cfd5"),
+            vec![CellDep::new(
+                h256("c7813f6a415144643970c2e88e0bb6ca6a8edc5dd7c1022746f628284a9936d5"),
+                0,
+                DepType::Code,
+            )],
+        ),
+        KnownScript::new(
+            SystemScript::Xudt,
+            CodeHashType::Data1,
+            h256("50bd8d6680b8b9cf98b73f3c08faf8b2a21914311954118ad6609be6e78a1b95"),
+            vec![CellDep::new(
+                h256("c07844ce21b38e4b071dd0e1ee3b0e27afd8d7532491327f39b786343f558ab7"),
+                0,
+                DepType::Code,
+            )],
+        ),
+        KnownScript::new(
+            SystemScript::Omnilock,
+            CodeHashType::Type,
+            h256("9b819793a64463aed77c615d6cb226eea5487ccfc0783043a587254cda2b6f26"),
+            vec![CellDep::new(
+                h256("c76edf469816aa22f416503c38d0b533d2a018e253e379f134c3985b3472c842"),
+                0,
+                DepType::Code,
+            )],
+        ),
+    ];
+    static ref TESTNET: Vec<KnownScript> = vec![
+        KnownScript::new(
+            SystemScript::Secp256k1Blake160,
+            CodeHashType::Type,
+            h256("9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8"),
+            vec![CellDep::new(
+                h256("f8de3bb47d055cdf460d93a2a6e1b05f7432f9777c8c474abf4eec1d4aee5d37"),
+                0,
+                DepType::DepGroup,
+            )],
+        ),
+        KnownScript::new(
+            SystemScript::Secp256k1MultiSig,
+            CodeHashType::Type,
+            h256("5c5069eb0857efc65e1bca0c07df34c31663b3622fd3876c876320fc9634e2a8"),
+            vec![CellDep::new(
+                h256("f8de3bb47d055cdf460d93a2a6e1b05f7432f9777c8c474abf4eec1d4aee5d37"),
+                1,
+                DepType::DepGroup,
+            )],
+        ),
+        KnownScript::new(
+            SystemScript::AnyoneCanPay,
+            CodeHashType::Type,
+            h256("3419a1c09eb2567f6552ee7a8ecffd64155cffe0f1796e6e61ec088d740c1356"),
+            vec![CellDep::new(
+                h256("ec26b0f85ed839ece5f11c4c4e837ec359f5adc4420410f6453b1f6b60fb96a6"),
+                0,
+                DepType::DepGroup,
+            )],
+        ),
+        KnownScript::new(
+            SystemScript::Cheque,
+            CodeHashType::Type,
+            h256("60d5f39efce409c587cb9ea359cefdead650ca128f0bd9cb3855348f98c70d5b"),
+            vec![CellDep::new(
+                h256("7f96858be0a9d584b4a9ea190e0420835156a6010a5fde15ffcdc9d9c721ccab"),
+                0,
+                DepType::DepGroup,
+            )],
+        ),
+        KnownScript::new(
+            SystemScript::NervosDao,
+            CodeHashType::Type,
+            h256("82d76d1b75fe2fd9a27dfbaa65a039221a380d76c926f378d3f81cf3e7e13f2e"),
+            vec![CellDep::new(
+                h256("8f8c79eb6671709633fe6a46de93c0fedc9c1b8a6527a18d3983879542635c9f"),
+                2,
+                DepType::Code,
+            )],
+        ),
+        KnownScript::new(
+            SystemScript::Sudt,
+            CodeHashType::Type,
+            h256("c5e5dcf215925f7ef4dfaf5f4b4f105bc321c02776d6e7d52a1db3fcd9d011a4"),
+            vec![CellDep::new(
+                h256("e12877ebd2c3c364dc46c5c992bcfaf4986ee1a6bdc4e4f0bd6d9d5d1ef0e8e5"),
+                0,
+                DepType::Code,
+            )],
+        ),
+        KnownScript::new(
+            SystemScript::Xudt,
+            CodeHashType::Type,
+            h256("25c29dc317811a6f6f3985a7a9ebc4838bd388d19d0feeecf0bcd60f6c0975bb"),
+            vec![CellDep::new(
+                h256("bf6fb538763efec2a70a6a3dcb7242787087e1030c4e7d86585bc63a9d337f5f"),
+                0,
+                DepType::Code,
+            )],
+        ),
+        KnownScript::new(
+            SystemScript::Omnilock,
+            CodeHashType::Type,
+            h256("f329effd1c475a2978453c8600e1eaf0bc2087ee093c3ee64cc96ec6847752cb"),
+            vec![CellDep::new(
+                h256("27b62d8be8ed80b9f56ee0fe41355becdb6f6a40aeba82d3900434f43b1c8b60"),
+                0,
+                DepType::Code,
+            )],
+        ),
+    ];
+}
+
+fn h256(hex: &str) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    faster_hex::hex_decode(hex.as_bytes(), &mut bytes).unwrap();
+    bytes
+}
+
+/// System Scripts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SystemScript {
+    Secp256k1Blake160,
+    Secp256k1MultiSig,
+    AnyoneCanPay,
+    Cheque,
+    NervosDao,
+    Sudt,
+    Xudt,
+    Omnilock,
+}
+
+/// Dep Type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DepType {
+    Code = 0x00,
+    DepGroup = 0x01,
+}
+
+/// Out Point
+#[derive(Property, Clone, PartialEq, Eq, Hash)]
+#[property(get(public), set(disable), mut(disable))]
+pub struct OutPoint {
+    tx_hash: [u8; 32],
+    #[property(get(type = "copy"))]
+    index: u32,
+}
+
+/// Cell Dep
+#[derive(Property, Debug, Clone, PartialEq, Eq, Hash)]
+#[property(get(public), set(disable), mut(disable))]
+pub struct CellDep {
+    out_point: OutPoint,
+    #[property(get(type = "copy"))]
+    dep_type: DepType,
+}
+
+/// Known Script
+///
+/// A deployed system script: the code hash, the hash type and the cell deps to use it.
+#[derive(Property, Clone, PartialEq, Eq, Hash)]
+#[property(get(public), set(disable), mut(disable))]
+pub struct KnownScript {
+    #[property(get(type = "copy"))]
+    system_script: SystemScript,
+    #[property(get(type = "copy"))]
+    hash_type: CodeHashType,
+    code_hash: [u8; CODE_HASH_SIZE],
+    cell_deps: Vec<CellDep>,
+}
+
+/// The known scripts on mainnet.
+pub fn mainnet() -> &'static [KnownScript] {
+    &MAINNET
+}
+
+/// The known scripts on testnet.
+pub fn testnet() -> &'static [KnownScript] {
+    &TESTNET
+}
+
+impl SystemScript {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Secp256k1Blake160 => "secp256k1_blake160_sighash_all",
+            Self::Secp256k1MultiSig => "secp256k1_blake160_multisig_all",
+            Self::AnyoneCanPay => "anyone_can_pay",
+            Self::Cheque => "cheque",
+            Self::NervosDao => "nervos_dao",
+            Self::Sudt => "sudt",
+            Self::Xudt => "xudt",
+            Self::Omnilock => "omnilock",
+        }
+    }
+}
+
+impl fmt::Display for SystemScript {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl fmt::Debug for OutPoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "OutPoint {{")?;
+        write!(f, " tx_hash: {}", utilities::hex_string(&self.tx_hash[..]))?;
+        write!(f, " , index: {}", self.index)?;
+        write!(f, " }}")
+    }
+}
+
+impl OutPoint {
+    pub fn new(tx_hash: [u8; 32], index: u32) -> Self {
+        Self { tx_hash, index }
+    }
+}
+
+impl CellDep {
+    pub fn new(tx_hash: [u8; 32], index: u32, dep_type: DepType) -> Self {
+        Self {
+            out_point: OutPoint::new(tx_hash, index),
+            dep_type,
+        }
+    }
+}
+
+impl fmt::Debug for KnownScript {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "KnownScript {{")?;
+        write!(f, " name: {}", self.system_script)?;
+        write!(
+            f,
+            " , code_hash: {}",
+            utilities::hex_string(&self.code_hash[..])
+        )?;
+        write!(f, " , hash_type: {:?}", self.hash_type)?;
+        write!(f, " , cell_deps: {:?}", self.cell_deps)?;
+        write!(f, " }}")
+    }
+}
+
+impl KnownScript {
+    pub fn new(
+        system_script: SystemScript,
+        hash_type: CodeHashType,
+        code_hash: [u8; CODE_HASH_SIZE],
+        cell_deps: Vec<CellDep>,
+    ) -> Self {
+        Self {
+            system_script,
+            hash_type,
+            code_hash,
+            cell_deps,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.system_script.name()
+    }
+
+    pub fn to_code_hash(&self) -> CodeHash {
+        CodeHash::Data {
+            hash_type: self.hash_type,
+            content: self.code_hash,
+        }
+    }
+
+    /// Checks if the code hash is the code hash of this script.
+    pub fn matches(&self, hash_type: CodeHashType, code_hash: &[u8]) -> bool {
+        self.hash_type == hash_type && self.code_hash[..] == code_hash[..]
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::{convert::TryInto, fmt};
+
+use property::Property;
+
+pub mod error;
+use error::{Error, Result};
+pub mod known;
+pub use known::{CellDep, DepType, KnownScript, OutPoint, SystemScript};
+
+use crate::{
+    address::{CodeHashType, CODE_HASH_SIZE},
+    blake2b, utilities,
+};
+
+#[cfg(test)]
+mod tests;
+
+const NUMBER_SIZE: usize = 4;
+const FIELDS_COUNT: usize = 3;
+const HEADER_SIZE: usize = NUMBER_SIZE * (1 + FIELDS_COUNT);
+
+/// Script
+///
+/// The molecule serialization is the same as the `Script` table in CKB.
+#[derive(Property, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[property(get(public), set(disable), mut(disable))]
+pub struct Script {
+    code_hash: [u8; CODE_HASH_SIZE],
+    #[property(get(type = "copy"))]
+    hash_type: CodeHashType,
+    args: Vec<u8>,
+}
+
+impl fmt::Debug for Script {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Script {{")?;
+        write!(
+            f,
+            " code_hash: {}",
+            utilities::hex_string(&self.code_hash[..])
+        )?;
+        write!(f, " , hash_type: {:?}", self.hash_type)?;
+        write!(f, " , args: {}", utilities::hex_string(&self.args[..]))?;
+        write!(f, " }}")
+    }
+}
+
+impl Script {
+    pub fn new(code_hash: [u8; CODE_HASH_SIZE], hash_type: CodeHashType, args: Vec<u8>) -> Self {
+        Self {
+            code_hash,
+            hash_type,
+            args,
+        }
+    }
+
+    pub fn into_parts(self) -> ([u8; CODE_HASH_SIZE], CodeHashType, Vec<u8>) {
+        let Self {
+            code_hash,
+            hash_type,
+            args,
+        } = self;
+        (code_hash, hash_type, args)
+    }
+
+    /// Serializes the script into molecule bytes.
+    pub fn serialize(&self) -> Vec<u8> {
+        let code_hash_offset = HEADER_SIZE;
+        let hash_type_offset = code_hash_offset + CODE_HASH_SIZE;
+        let args_offset = hash_type_offset + 1;
+        let total_size = args_offset + NUMBER_SIZE + self.args.len();
+        let mut buf = Vec::with_capacity(total_size);
+        for number in &[total_size, code_hash_offset, hash_type_offset, args_offset] {
+            buf.extend_from_slice(&(*number as u32).to_le_bytes());
+        }
+        buf.extend_from_slice(&self.code_hash[..]);
+        buf.push(self.hash_type.value());
+        buf.extend_from_slice(&(self.args.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&self.args[..]);
+        buf
+    }
+
+    /// Deserializes the script from molecule bytes.
+    pub fn from_slice(data: &[u8]) -> Result<Self> {
+        let read_number = |offset: usize| -> usize {
+            let bytes = data[offset..offset + NUMBER_SIZE].try_into().unwrap();
+            u32::from_le_bytes(bytes) as usize
+        };
+        if data.len() < HEADER_SIZE {
+            return Err(Error::Molecule("header is not enough".to_owned()));
+        }
+        if read_number(0) != data.len() {
+            return Err(Error::Molecule("total size is not matched".to_owned()));
+        }
+        if read_number(NUMBER_SIZE) != HEADER_SIZE {
+            return Err(Error::Molecule("fields count is not matched".to_owned()));
+        }
+        let hash_type_offset = read_number(NUMBER_SIZE * 2);
+        let args_offset = read_number(NUMBER_SIZE * 3);
+        if hash_type_offset != HEADER_SIZE + CODE_HASH_SIZE || args_offset != hash_type_offset + 1 {
+            return Err(Error::Molecule("field offsets are not matched".to_owned()));
+        }
+        if data.len() < args_offset + NUMBER_SIZE {
+            return Err(Error::Molecule("args header is not enough".to_owned()));
+        }
+        let args_size = read_number(args_offset);
+        if args_offset + NUMBER_SIZE + args_size != data.len() {
+            return Err(Error::Molecule("args size is not matched".to_owned()));
+        }
+        let mut code_hash = [0u8; CODE_HASH_SIZE];
+        code_hash.copy_from_slice(&data[HEADER_SIZE..hash_type_offset]);
+        let hash_type = CodeHashType::from_value(data[hash_type_offset])
+            .map_err(|_| Error::UnknownHashType(data[hash_type_offset]))?;
+        let args = data[args_offset + NUMBER_SIZE..].to_vec();
+        Ok(Self::new(code_hash, hash_type, args))
+    }
+
+    /// Calculates the script hash (for lock scripts, it's the lock hash).
+    pub fn hash(&self) -> [u8; 32] {
+        blake2b::blake2b_256(self.serialize())
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use slices::u8_slice;
+use std::{convert::TryFrom, str::FromStr};
+
+use super::{known, DepType, Script, SystemScript};
+use crate::{
+    address::{
+        Address, AddressBuilder, AddressFormat, CodeHash, CodeHashIndex, CodeHashType, Network,
+    },
+    blake2b,
+};
+
+#[test]
+fn test_molecule() {
+    let code_hash = u8_slice!("0x9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8");
+    let args = u8_slice!("0xb39bbc0b3673c7d36450bc14cfcdad2d559c6c64");
+    let expected = u8_slice!(
+        "0x49000000100000003000000031000000\
+         9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8\
+         01\
+         14000000b39bbc0b3673c7d36450bc14cfcdad2d559c6c64"
+    );
+    let script = Script::new(*code_hash, CodeHashType::Type, args.to_vec());
+    let actual = script.serialize();
+    assert_eq!(&expected[..], &actual[..]);
+    assert_eq!(script.hash(), blake2b::blake2b_256(&actual));
+    let decoded = Script::from_slice(&actual).unwrap();
+    assert_eq!(decoded, script);
+    assert!(Script::from_slice(&actual[..actual.len() - 1]).is_err());
+    let mut invalid = actual.clone();
+    invalid[48] = 0x03;
+    assert!(Script::from_slice(&invalid).is_err());
+}
+
+#[test]
+fn test_address_conversions() {
+    let short = Address::from_str("ckb1qyqt8xaupvm8837nv3gtc9x0ekkj64vud3jqfwyw5v").unwrap();
+    let full = Address::from_str(
+        "ckb1qzda0cr08m85hc8jlnfp3zer7xulejywt49kt2rr0vthywaa50xwsqdnnw7qkdnnclfkg59uzn8umt\
+         fd2kwxceqxwquc4",
+    )
+    .unwrap();
+    let script = short.to_script();
+    assert_eq!(script, full.to_script());
+    assert_eq!(script, Script::from(&full));
+    let address = Address::try_from((script.clone(), Network::Main)).unwrap();
+    assert_eq!(address.format(), AddressFormat::Full);
+    assert_eq!(address.to_string(), full.to_string());
+    assert_eq!(address.to_script(), script);
+    for network in &[Network::Main, Network::Test] {
+        let address = Address::from_script(script.clone(), *network).unwrap();
+        if let CodeHash::Data { hash_type, content } = address.code_hash() {
+            assert_eq!(*hash_type, script.hash_type());
+            assert_eq!(content, script.code_hash());
+        } else {
+            panic!("code hash should be data");
+        }
+        assert_eq!(address.to_script(), script);
+    }
+}
+
+#[test]
+fn test_known_scripts() {
+    for scripts in &[known::mainnet(), known::testnet()] {
+        assert_eq!(scripts.len(), 8);
+        for (i, script) in scripts.iter().enumerate() {
+            assert!(!script.cell_deps().is_empty());
+            assert!(scripts[..i]
+                .iter()
+                .all(|s| s.system_script() != script.system_script()));
+        }
+    }
+
+    let short = Address::from_str("ckb1qyqt8xaupvm8837nv3gtc9x0ekkj64vud3jqfwyw5v").unwrap();
+    let script = short.known_script().unwrap();
+    assert_eq!(script.system_script(), SystemScript::Secp256k1Blake160);
+    assert_eq!(script.name(), "secp256k1_blake160_sighash_all");
+    let cell_dep = &script.cell_deps()[0];
+    assert_eq!(cell_dep.dep_type(), DepType::DepGroup);
+    assert_eq!(
+        cell_dep.out_point().tx_hash(),
+        u8_slice!("0x71a7ba8fc96349fea0ed3a5c47992e3b4084b031a42264a018e0072e8172e46c")
+    );
+    assert_eq!(cell_dep.out_point().index(), 0);
+    let full = short.convert(AddressFormat::Full).unwrap();
+    assert_eq!(full.known_script(), Some(script));
+
+    let acp = CodeHash::Index(CodeHashIndex::AnyoneCanPay);
+    let mainnet_acp = acp.known_script(Network::Main).unwrap();
+    let testnet_acp = acp.known_script(Network::Test).unwrap();
+    assert_eq!(mainnet_acp.system_script(), SystemScript::AnyoneCanPay);
+    assert_eq!(testnet_acp.system_script(), SystemScript::AnyoneCanPay);
+    assert_ne!(mainnet_acp.code_hash(), testnet_acp.code_hash());
+
+    let dao = CodeHash::Data {
+        hash_type: CodeHashType::Type,
+        content: *u8_slice!("0x82d76d1b75fe2fd9a27dfbaa65a039221a380d76c926f378d3f81cf3e7e13f2e"),
+    };
+    let script = dao.known_script(Network::Test).unwrap();
+    assert_eq!(script.system_script(), SystemScript::NervosDao);
+    assert_eq!(script.cell_deps()[0].dep_type(), DepType::Code);
+    assert_eq!(
+        Network::Test
+            .spec()
+            .known_script_of(SystemScript::NervosDao),
+        Some(script)
+    );
+    let dao_data = CodeHash::Data {
+        hash_type: CodeHashType::Data,
+        content: *u8_slice!("0x82d76d1b75fe2fd9a27dfbaa65a039221a380d76c926f378d3f81cf3e7e13f2e"),
+    };
+    assert!(dao_data.known_script(Network::Test).is_none());
+
+    let unknown = AddressBuilder::default()
+        .code_hash_by_data(CodeHashType::Data, [0u8; 32])
+        .args_simple(vec![0u8; 20])
+        .build()
+        .unwrap();
+    assert!(unknown.known_script().is_none());
+}
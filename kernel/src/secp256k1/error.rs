@@ -0,0 +1,21 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PublicKeyError {
+    #[error("invalid hex string: {0}")]
+    InvalidHex(String),
+    #[error("invalid public key length: {0}")]
+    InvalidLength(usize),
+    #[error("invalid public key prefix: {0:#04x}")]
+    InvalidPrefix(u8),
+    #[error("the point is not on the curve")]
+    NotOnCurve,
+}
@@ -8,20 +8,27 @@
 
 use lazy_static::lazy_static;
 
-pub(self) use secp256k1_kernel as kernel;
+use secp256k1_kernel as kernel;
 
 lazy_static! {
     pub(self) static ref SECP256K1: kernel::Secp256k1<kernel::All> = kernel::Secp256k1::new();
 }
 
+mod error;
 mod public;
 mod secret;
 mod signature;
+mod standard;
 
+pub use error::PublicKeyError;
 pub use kernel::Error;
-pub use public::PublicKey;
+pub use public::{PublicKey, PublicKeyFormat};
 pub use secret::SecretKey;
 pub use signature::Signature;
+pub use standard::StandardSignature;
+
+#[cfg(feature = "serde")]
+pub use crate::serde_impls::secret_key as serde_secret_key;
 
 #[cfg(test)]
 mod tests;
@@ -6,9 +6,26 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use super::{kernel, Error, SecretKey, SECP256K1};
+use std::str;
+
+use super::{kernel, Error, PublicKeyError, SecretKey, Signature, StandardSignature, SECP256K1};
 use crate::{blake2b, utilities, PubKeyHash};
 
+const COMPRESSED_SIZE: usize = 33;
+const UNCOMPRESSED_SIZE: usize = 65;
+const X_ONLY_SIZE: usize = 32;
+
+/// The serialization formats of a public key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublicKeyFormat {
+    /// 33 bytes, the parity of y (`0x02` or `0x03`) and x.
+    Compressed,
+    /// 65 bytes, `0x04`, x and y.
+    Uncompressed,
+    /// 32 bytes, only x, with an even y implied (BIP-340).
+    XOnly,
+}
+
 #[derive(Clone, Copy, PartialOrd, Ord, PartialEq, Eq)]
 pub struct PublicKey(pub(super) kernel::PublicKey);
 
@@ -27,6 +44,13 @@ impl ::std::fmt::Display for PublicKey {
     }
 }
 
+impl str::FromStr for PublicKey {
+    type Err = PublicKeyError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(s)
+    }
+}
+
 impl PublicKey {
     pub fn from_secret_key(sk: &SecretKey) -> Self {
         Self(kernel::PublicKey::from_secret_key(&*SECP256K1, sk.as_ref()))
@@ -36,7 +60,106 @@ impl PublicKey {
         kernel::PublicKey::from_slice(data).map(Self)
     }
 
+    /// Parses a public key in any of the formats, which is detected by the length.
+    pub fn parse(data: &[u8]) -> Result<Self, PublicKeyError> {
+        let format = match data.len() {
+            COMPRESSED_SIZE => PublicKeyFormat::Compressed,
+            UNCOMPRESSED_SIZE => PublicKeyFormat::Uncompressed,
+            X_ONLY_SIZE => PublicKeyFormat::XOnly,
+            len => return Err(PublicKeyError::InvalidLength(len)),
+        };
+        Self::parse_as(data, format)
+    }
+
+    /// Parses a public key in the format.
+    pub fn parse_as(data: &[u8], format: PublicKeyFormat) -> Result<Self, PublicKeyError> {
+        let (size, prefixes): (_, &[u8]) = match format {
+            PublicKeyFormat::Compressed => (COMPRESSED_SIZE, &[0x02, 0x03]),
+            PublicKeyFormat::Uncompressed => (UNCOMPRESSED_SIZE, &[0x04]),
+            PublicKeyFormat::XOnly => (X_ONLY_SIZE, &[]),
+        };
+        if data.len() != size {
+            return Err(PublicKeyError::InvalidLength(data.len()));
+        }
+        let result = if format == PublicKeyFormat::XOnly {
+            let mut compressed = [0x02; COMPRESSED_SIZE];
+            compressed[1..].copy_from_slice(data);
+            kernel::PublicKey::from_slice(&compressed[..])
+        } else if !prefixes.contains(&data[0]) {
+            return Err(PublicKeyError::InvalidPrefix(data[0]));
+        } else {
+            kernel::PublicKey::from_slice(data)
+        };
+        result.map(Self).map_err(|_| PublicKeyError::NotOnCurve)
+    }
+
+    /// Parses a public key from a hex string, with or without the `0x` prefix.
+    pub fn from_hex(s: &str) -> Result<Self, PublicKeyError> {
+        let hex = if s.starts_with("0x") { &s[2..] } else { s }.as_bytes();
+        if hex.len() & 1 != 0 {
+            return Err(PublicKeyError::InvalidHex(s.to_owned()));
+        }
+        let mut data = vec![0u8; hex.len() / 2];
+        faster_hex::hex_decode(hex, &mut data)
+            .map_err(|_| PublicKeyError::InvalidHex(s.to_owned()))?;
+        Self::parse(&data)
+    }
+
+    pub fn to_compressed(&self) -> [u8; COMPRESSED_SIZE] {
+        self.0.serialize()
+    }
+
+    pub fn to_uncompressed(&self) -> [u8; UNCOMPRESSED_SIZE] {
+        self.0.serialize_uncompressed()
+    }
+
+    /// Returns the x coordinate only, the parity of y is lost.
+    pub fn to_x_only(&self) -> [u8; X_ONLY_SIZE] {
+        let mut data = [0u8; X_ONLY_SIZE];
+        data.copy_from_slice(&self.0.serialize()[1..]);
+        data
+    }
+
+    pub fn serialize_as(&self, format: PublicKeyFormat) -> Vec<u8> {
+        match format {
+            PublicKeyFormat::Compressed => self.to_compressed().to_vec(),
+            PublicKeyFormat::Uncompressed => self.to_uncompressed().to_vec(),
+            PublicKeyFormat::XOnly => self.to_x_only().to_vec(),
+        }
+    }
+
+    pub fn to_hex(&self, format: PublicKeyFormat) -> String {
+        utilities::hex_string(&self.serialize_as(format))
+    }
+
+    /// Verifies the signature of the message by recovering the public key, the same as the
+    /// lock script does.
+    ///
+    /// A signature with a high S value is accepted, see [`verify_standard`](#method.verify_standard)
+    /// for the strict check.
+    pub fn verify(&self, input: &[u8], sig: &Signature) -> Result<(), Error> {
+        if sig.recover(input)? == *self {
+            Ok(())
+        } else {
+            Err(Error::IncorrectSignature)
+        }
+    }
+
+    /// Verifies the signature without the recovery id, which should have a low S value.
+    pub fn verify_standard(&self, input: &[u8], sig: &StandardSignature) -> Result<(), Error> {
+        let msg = kernel::Message::from_slice(input)?;
+        SECP256K1.verify(&msg, &sig.0, &self.0)
+    }
+
+    /// Adds the tweak times the generator to the public key.
+    pub(crate) fn add_exp_tweak(&self, tweak: &[u8]) -> Result<Self, Error> {
+        let mut pk = *self;
+        pk.0.add_exp_assign(&*SECP256K1, tweak)?;
+        Ok(pk)
+    }
+
+    /// Hashes the compressed form, whichever format the key was parsed from.
     pub fn pkhash_blake160(&self) -> PubKeyHash {
-        PubKeyHash::from_secp256k1_blake160(blake2b::blake160(&self.0.serialize()[..]))
+        PubKeyHash::from_secp256k1_blake160(blake2b::blake160(&self.to_compressed()[..]))
     }
 }
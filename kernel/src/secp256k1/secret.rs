@@ -59,10 +59,17 @@ impl SecretKey {
 
     pub fn sign_recoverable(&self, input: &[u8]) -> Result<Signature, Error> {
         kernel::Message::from_slice(input)
-            .map(|msg| (&*SECP256K1).sign_recoverable(&msg, self))
+            .map(|msg| SECP256K1.sign_recoverable(&msg, self))
             .map(Signature)
     }
 
+    /// Adds the tweak to the secret key, modulo the curve order.
+    pub(crate) fn add_tweak(&self, tweak: &[u8]) -> Result<Self, Error> {
+        let mut sk = self.clone();
+        sk.0.add_assign(tweak)?;
+        Ok(sk)
+    }
+
     pub(crate) fn zeroize(&mut self) {
         let Self(inner) = self;
         let dst = inner.as_mut_ptr();
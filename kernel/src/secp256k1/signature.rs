@@ -6,7 +6,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use super::{kernel, Error};
+use super::{kernel, Error, PublicKey, StandardSignature, SECP256K1};
 use crate::utilities;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -27,7 +27,10 @@ impl ::std::fmt::Display for Signature {
     }
 }
 
+const ETHEREUM_V_BASE: u8 = 27;
+
 impl Signature {
+    /// Decodes the 65-byte compact signature with the recovery id, `r || s || id`.
     pub fn from_bytes(bytes: &[u8; 65]) -> Result<Self, Error> {
         let id = kernel::recovery::RecoveryId::from_i32(i32::from(bytes[64]))?;
         kernel::recovery::RecoverableSignature::from_compact(&bytes[..64], id).map(Self)
@@ -40,4 +43,60 @@ impl Signature {
         bytes[64] = id.to_i32() as u8;
         bytes
     }
+
+    /// Decodes an Ethereum style signature, `r || s || v` where `v` is 27 or 28.
+    pub fn from_eth_bytes(bytes: &[u8; 65]) -> Result<Self, Error> {
+        match bytes[64] {
+            v @ 27..=28 => {
+                let mut bytes = *bytes;
+                bytes[64] = v - ETHEREUM_V_BASE;
+                Self::from_bytes(&bytes)
+            }
+            _ => Err(Error::InvalidRecoveryId),
+        }
+    }
+
+    /// Encodes as an Ethereum style signature, `r || s || v` where `v` is 27 plus the recovery id.
+    ///
+    /// Returns an error if the recovery id is 2 or 3, which could not be encoded.
+    pub fn to_eth_bytes(self) -> Result<[u8; 65], Error> {
+        let mut bytes = self.to_bytes();
+        if bytes[64] > 1 {
+            return Err(Error::InvalidRecoveryId);
+        }
+        bytes[64] += ETHEREUM_V_BASE;
+        Ok(bytes)
+    }
+
+    pub fn to_standard(self) -> StandardSignature {
+        StandardSignature(self.0.to_standard())
+    }
+
+    /// Checks if the S value is in the lower half of the curve order.
+    pub fn is_low_s(&self) -> bool {
+        self.to_standard().is_low_s()
+    }
+
+    /// Returns the signature with the S value normalized into the lower half.
+    ///
+    /// Negating S flips the parity of the recovery id, so the same public key is recovered.
+    pub fn to_low_s(self) -> Self {
+        if self.is_low_s() {
+            return self;
+        }
+        let (id, _) = self.serialize_compact();
+        let data = self.to_standard().to_low_s().to_compact();
+        let id = kernel::recovery::RecoveryId::from_i32(id.to_i32() ^ 1)
+            .unwrap_or_else(|_| unreachable!());
+        kernel::recovery::RecoverableSignature::from_compact(&data[..], id)
+            .map(Self)
+            .unwrap_or_else(|_| unreachable!())
+    }
+
+    /// Recovers the public key which signed the message.
+    pub fn recover(&self, input: &[u8]) -> Result<PublicKey, Error> {
+        kernel::Message::from_slice(input)
+            .and_then(|msg| SECP256K1.recover(&msg, &self.0))
+            .map(PublicKey)
+    }
 }
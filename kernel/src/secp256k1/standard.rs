@@ -0,0 +1,77 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::{kernel, Error, PublicKey, Signature};
+use crate::utilities;
+
+/// Signature without the recovery id.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct StandardSignature(pub(super) kernel::Signature);
+
+impl_std_traits!(StandardSignature, kernel::Signature);
+
+impl ::std::fmt::Debug for StandardSignature {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl ::std::fmt::Display for StandardSignature {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let bytes = self.to_compact();
+        write!(f, "{}", utilities::hex_string(&bytes))
+    }
+}
+
+impl StandardSignature {
+    /// Decodes a strict DER signature.
+    pub fn from_der(data: &[u8]) -> Result<Self, Error> {
+        kernel::Signature::from_der(data).map(Self)
+    }
+
+    pub fn to_der(self) -> Vec<u8> {
+        self.0.serialize_der().to_vec()
+    }
+
+    /// Decodes a 64-byte compact signature, `r || s`.
+    pub fn from_compact(bytes: &[u8; 64]) -> Result<Self, Error> {
+        kernel::Signature::from_compact(&bytes[..]).map(Self)
+    }
+
+    pub fn to_compact(self) -> [u8; 64] {
+        self.0.serialize_compact()
+    }
+
+    /// Checks if the S value is in the lower half of the curve order.
+    ///
+    /// A signature with a high S is malleable, and it is rejected by the verification.
+    pub fn is_low_s(&self) -> bool {
+        self.to_low_s() == *self
+    }
+
+    /// Returns the signature with the S value normalized into the lower half.
+    pub fn to_low_s(self) -> Self {
+        let mut sig = self;
+        sig.0.normalize_s();
+        sig
+    }
+
+    /// Finds the recovery id with which the signature recovers the public key.
+    pub fn to_recoverable(self, input: &[u8], pk: &PublicKey) -> Result<Signature, Error> {
+        let data = self.to_compact();
+        for id in 0..4 {
+            let id = kernel::recovery::RecoveryId::from_i32(id)?;
+            let sig = kernel::recovery::RecoverableSignature::from_compact(&data[..], id)
+                .map(Signature)?;
+            if sig.recover(input).ok().as_ref() == Some(pk) {
+                return Ok(sig);
+            }
+        }
+        Err(Error::IncorrectSignature)
+    }
+}
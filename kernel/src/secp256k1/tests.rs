@@ -6,7 +6,12 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use super::{kernel, SecretKey};
+use slices::u8_slice;
+use std::str::FromStr;
+
+use super::{
+    kernel, PublicKey, PublicKeyError, PublicKeyFormat, SecretKey, Signature, StandardSignature,
+};
 
 #[test]
 fn test_random() {
@@ -29,3 +34,192 @@ fn test_zeroize() {
         assert_eq!(sk0[i], 0);
     }
 }
+
+#[test]
+fn test_recover_and_verify() {
+    let sk = SecretKey::from_seed(1);
+    let pk = sk.public_key();
+    let msg = [0x5a; 32];
+    let sig = sk.sign_recoverable(&msg[..]).unwrap();
+    assert_eq!(sig.recover(&msg[..]).unwrap(), pk);
+    assert!(pk.verify(&msg[..], &sig).is_ok());
+    assert!(pk.pkhash_blake160().verify(&msg[..], &sig).is_ok());
+
+    let other = [0xa5; 32];
+    assert_ne!(sig.recover(&other[..]).unwrap(), pk);
+    assert_matches!(
+        pk.verify(&other[..], &sig),
+        Err(kernel::Error::IncorrectSignature)
+    );
+    assert_matches!(
+        SecretKey::from_seed(2)
+            .public_key()
+            .pkhash_blake160()
+            .verify(&msg[..], &sig),
+        Err(kernel::Error::IncorrectSignature)
+    );
+    assert_matches!(sig.recover(&msg[1..]), Err(kernel::Error::InvalidMessage));
+}
+
+// The order of the curve.
+const N: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+// Replaces S with N - S.
+fn negate_s(bytes: &mut [u8]) {
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let diff = i16::from(N[i]) - i16::from(bytes[32 + i]) - borrow;
+        borrow = if diff < 0 { 1 } else { 0 };
+        bytes[32 + i] = (diff + (borrow << 8)) as u8;
+    }
+}
+
+#[test]
+fn test_encodings() {
+    let sk = SecretKey::from_seed(1);
+    let pk = sk.public_key();
+    let msg = [0x5a; 32];
+    let sig = sk.sign_recoverable(&msg[..]).unwrap();
+    let standard = sig.to_standard();
+
+    let der = standard.to_der();
+    assert_eq!(der[0], 0x30);
+    assert_eq!(StandardSignature::from_der(&der).unwrap(), standard);
+    assert!(StandardSignature::from_der(&der[1..]).is_err());
+    let compact = standard.to_compact();
+    assert_eq!(&compact[..], &sig.to_bytes()[..64]);
+    assert_eq!(StandardSignature::from_compact(&compact).unwrap(), standard);
+    assert!(pk.verify_standard(&msg[..], &standard).is_ok());
+    assert_eq!(standard.to_recoverable(&msg[..], &pk).unwrap(), sig);
+    assert_matches!(
+        standard.to_recoverable(&msg[..], &SecretKey::from_seed(2).public_key()),
+        Err(kernel::Error::IncorrectSignature)
+    );
+
+    let mut ids = [false; 2];
+    for seed in 0..32 {
+        let sig = SecretKey::from_seed(seed)
+            .sign_recoverable(&msg[..])
+            .unwrap();
+        let id = sig.to_bytes()[64];
+        ids[usize::from(id)] = true;
+        let eth = sig.to_eth_bytes().unwrap();
+        assert_eq!(eth[64], id + 27);
+        assert_eq!(Signature::from_eth_bytes(&eth).unwrap(), sig);
+    }
+    assert_eq!(ids, [true, true]);
+    let mut invalid = sig.to_eth_bytes().unwrap();
+    invalid[64] = 1;
+    assert_matches!(
+        Signature::from_eth_bytes(&invalid),
+        Err(kernel::Error::InvalidRecoveryId)
+    );
+    for id in 2..4 {
+        let mut bytes = sig.to_bytes();
+        bytes[64] = id;
+        let sig = Signature::from_bytes(&bytes).unwrap();
+        assert_matches!(sig.to_eth_bytes(), Err(kernel::Error::InvalidRecoveryId));
+    }
+}
+
+#[test]
+fn test_low_s() {
+    let sk = SecretKey::from_seed(1);
+    let pk = sk.public_key();
+    let msg = [0x5a; 32];
+    let sig = sk.sign_recoverable(&msg[..]).unwrap();
+    assert!(sig.is_low_s());
+    assert_eq!(sig.to_low_s(), sig);
+
+    let mut bytes = sig.to_bytes();
+    negate_s(&mut bytes[..]);
+    let mut compact = [0u8; 64];
+    compact.copy_from_slice(&bytes[..64]);
+    let standard = StandardSignature::from_compact(&compact).unwrap();
+    assert!(!standard.is_low_s());
+    assert_eq!(standard.to_low_s(), sig.to_standard());
+    assert_matches!(
+        pk.verify_standard(&msg[..], &standard),
+        Err(kernel::Error::IncorrectSignature)
+    );
+
+    bytes[64] ^= 1;
+    let malleated = Signature::from_bytes(&bytes).unwrap();
+    assert!(!malleated.is_low_s());
+    assert_eq!(malleated.recover(&msg[..]).unwrap(), pk);
+    // The recoverable check accepts the malleated signature, but the strict check doesn't.
+    assert!(pk.verify(&msg[..], &malleated).is_ok());
+    assert!(pk.pkhash_blake160().verify(&msg[..], &malleated).is_ok());
+    assert_matches!(
+        pk.verify_standard(&msg[..], &malleated.to_standard()),
+        Err(kernel::Error::IncorrectSignature)
+    );
+    assert_eq!(malleated.to_low_s(), sig);
+}
+
+#[test]
+fn test_public_key_formats() {
+    let sk = SecretKey::from_slice(u8_slice!(
+        "0x0000000000000000000000000000000000000000000000000000000000000001"
+    ))
+    .unwrap();
+    let pk = sk.public_key();
+    let compressed = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    let uncompressed = "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+                        483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+    let x_only = &compressed[2..];
+    assert_eq!(pk.to_hex(PublicKeyFormat::Compressed), compressed);
+    assert_eq!(pk.to_hex(PublicKeyFormat::Uncompressed), uncompressed);
+    assert_eq!(pk.to_hex(PublicKeyFormat::XOnly), x_only);
+    assert_eq!(pk.to_string(), compressed);
+    for hex in &[compressed, uncompressed, x_only] {
+        let parsed = PublicKey::from_hex(hex).unwrap();
+        assert_eq!(parsed, pk);
+        assert_eq!(PublicKey::from_str(&format!("0x{}", hex)).unwrap(), pk);
+        assert_eq!(
+            parsed.pkhash_blake160().as_slice(),
+            pk.pkhash_blake160().as_slice()
+        );
+    }
+
+    // The x-only form implies an even y.
+    let odd = SecretKey::from_seed(1).public_key();
+    let odd = if odd.to_compressed()[0] == 0x03 {
+        odd
+    } else {
+        SecretKey::from_seed(2).public_key()
+    };
+    let even = PublicKey::parse(&odd.to_x_only()[..]).unwrap();
+    assert_ne!(even, odd);
+    assert_eq!(even.to_x_only(), odd.to_x_only());
+
+    assert_matches!(
+        PublicKey::from_hex("0x02zz"),
+        Err(PublicKeyError::InvalidHex(_))
+    );
+    assert_matches!(
+        PublicKey::from_hex("0x0279"),
+        Err(PublicKeyError::InvalidLength(2))
+    );
+    let mut data = pk.to_compressed();
+    data[0] = 0x04;
+    assert_matches!(
+        PublicKey::parse(&data[..]),
+        Err(PublicKeyError::InvalidPrefix(0x04))
+    );
+    assert_matches!(
+        PublicKey::parse_as(&data[1..], PublicKeyFormat::Compressed),
+        Err(PublicKeyError::InvalidLength(32))
+    );
+    data[0] = 0x02;
+    for byte in data[1..].iter_mut() {
+        *byte = 0xff;
+    }
+    assert_matches!(PublicKey::parse(&data[..]), Err(PublicKeyError::NotOnCurve));
+    let mut data = pk.to_uncompressed();
+    data[64] ^= 1;
+    assert_matches!(PublicKey::parse(&data[..]), Err(PublicKeyError::NotOnCurve));
+}
@@ -0,0 +1,345 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Serde support with the string forms of CKB JSON-RPC.
+//!
+//! Addresses are bech32 strings, bytes are `0x`-prefixed hex strings.
+
+use std::{fmt, str::FromStr};
+
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    address::{Address, Args, CodeHash, CodeHashIndex, CodeHashType, Network, Since},
+    secp256k1::{PublicKey, Signature, StandardSignature},
+    utilities, PubKeyHash, Script,
+};
+
+#[cfg(test)]
+mod tests;
+
+fn serialize_hex<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format!("0x{}", utilities::hex_string(bytes)))
+}
+
+fn deserialize_hex<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    if !s.starts_with("0x") || s.len() % 2 != 0 {
+        return Err(de::Error::custom(format!("invalid hex string: {}", s)));
+    }
+    let mut bytes = vec![0u8; (s.len() - 2) / 2];
+    faster_hex::hex_decode(&s.as_bytes()[2..], &mut bytes)
+        .map_err(|_| de::Error::custom(format!("invalid hex string: {}", s)))?;
+    Ok(bytes)
+}
+
+fn deserialize_into<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    buf: &mut [u8],
+) -> Result<(), D::Error> {
+    let bytes = deserialize_hex(deserializer)?;
+    if bytes.len() != buf.len() {
+        return Err(de::Error::invalid_length(bytes.len(), &"fixed size bytes"));
+    }
+    buf.copy_from_slice(&bytes);
+    Ok(())
+}
+
+fn deserialize_hash<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 32], D::Error> {
+    let mut hash = [0u8; 32];
+    deserialize_into(deserializer, &mut hash)?;
+    Ok(hash)
+}
+
+fn deserialize_from_str<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let s = String::deserialize(deserializer)?;
+    T::from_str(&s).map_err(de::Error::custom)
+}
+
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let address = self.encode().map_err(ser::Error::custom)?;
+        serializer.serialize_str(&address)
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_from_str(deserializer)
+    }
+}
+
+impl Serialize for Network {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.value())
+    }
+}
+
+impl<'de> Deserialize<'de> for Network {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::from_value(&s).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for CodeHashType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let s = match self {
+            Self::Data => "data",
+            Self::Type => "type",
+            Self::Data1 => "data1",
+            Self::Data2 => "data2",
+        };
+        serializer.serialize_str(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for CodeHashType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        match s.as_str() {
+            "data" => Ok(Self::Data),
+            "type" => Ok(Self::Type),
+            "data1" => Ok(Self::Data1),
+            "data2" => Ok(Self::Data2),
+            _ => Err(de::Error::unknown_variant(
+                &s,
+                &["data", "type", "data1", "data2"],
+            )),
+        }
+    }
+}
+
+impl Serialize for CodeHashIndex {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let s = match self {
+            Self::Secp256k1Blake160 => "secp256k1_blake160",
+            Self::Secp256k1MultiSig => "secp256k1_multisig",
+            Self::AnyoneCanPay => "anyone_can_pay",
+        };
+        serializer.serialize_str(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for CodeHashIndex {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        match s.as_str() {
+            "secp256k1_blake160" => Ok(Self::Secp256k1Blake160),
+            "secp256k1_multisig" => Ok(Self::Secp256k1MultiSig),
+            "anyone_can_pay" => Ok(Self::AnyoneCanPay),
+            _ => Err(de::Error::unknown_variant(
+                &s,
+                &["secp256k1_blake160", "secp256k1_multisig", "anyone_can_pay"],
+            )),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CodeHashIndexRepr {
+    index: CodeHashIndex,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CodeHashDataRepr {
+    #[serde(
+        serialize_with = "serialize_hex",
+        deserialize_with = "deserialize_hash"
+    )]
+    code_hash: [u8; 32],
+    hash_type: CodeHashType,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum CodeHashRepr {
+    Index(CodeHashIndexRepr),
+    Data(CodeHashDataRepr),
+}
+
+impl Serialize for CodeHash {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let repr = match *self {
+            Self::Index(index) => CodeHashRepr::Index(CodeHashIndexRepr { index }),
+            Self::Data { hash_type, content } => CodeHashRepr::Data(CodeHashDataRepr {
+                code_hash: content,
+                hash_type,
+            }),
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CodeHash {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code_hash = match CodeHashRepr::deserialize(deserializer)? {
+            CodeHashRepr::Index(CodeHashIndexRepr { index }) => Self::Index(index),
+            CodeHashRepr::Data(CodeHashDataRepr {
+                code_hash,
+                hash_type,
+            }) => Self::Data {
+                hash_type,
+                content: code_hash,
+            },
+        };
+        Ok(code_hash)
+    }
+}
+
+impl Serialize for Args {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut args = Vec::new();
+        self.serialize_into(&mut args);
+        serialize_hex(&args, serializer)
+    }
+}
+
+/// The args are always deserialized as `Args::Simple`.
+impl<'de> Deserialize<'de> for Args {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_hex(deserializer).map(Self::Simple)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScriptRepr {
+    #[serde(
+        serialize_with = "serialize_hex",
+        deserialize_with = "deserialize_hash"
+    )]
+    code_hash: [u8; 32],
+    hash_type: CodeHashType,
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
+    args: Vec<u8>,
+}
+
+impl Serialize for Script {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (code_hash, hash_type, args) = self.clone().into_parts();
+        ScriptRepr {
+            code_hash,
+            hash_type,
+            args,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Script {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let ScriptRepr {
+            code_hash,
+            hash_type,
+            args,
+        } = ScriptRepr::deserialize(deserializer)?;
+        Ok(Self::new(code_hash, hash_type, args))
+    }
+}
+
+/// The since is a `0x`-prefixed hex number, as `Uint64` in CKB JSON-RPC.
+impl Serialize for Since {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{:#x}", self.value()))
+    }
+}
+
+impl<'de> Deserialize<'de> for Since {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        if !s.starts_with("0x") {
+            return Err(de::Error::custom(format!("invalid since: {}", s)));
+        }
+        let value = u64::from_str_radix(&s[2..], 16).map_err(de::Error::custom)?;
+        Self::from_value(value).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for PubKeyHash {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_hex(self.as_slice(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PubKeyHash {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut hash = [0u8; 20];
+        deserialize_into(deserializer, &mut hash)?;
+        Ok(Self::from_secp256k1_blake160(hash))
+    }
+}
+
+/// The public key is in the compressed form.
+impl Serialize for PublicKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_hex(&(**self).serialize()[..], serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserialize_hex(deserializer)?;
+        Self::from_slice(&bytes).map_err(de::Error::custom)
+    }
+}
+
+/// The signature is the 65 bytes recoverable signature.
+impl Serialize for Signature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_hex(&self.to_bytes()[..], serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Signature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut bytes = [0u8; 65];
+        deserialize_into(deserializer, &mut bytes)?;
+        Self::from_bytes(&bytes).map_err(de::Error::custom)
+    }
+}
+
+/// The standard signature is the 64 bytes compact signature.
+impl Serialize for StandardSignature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_hex(&self.to_compact()[..], serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StandardSignature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut bytes = [0u8; 64];
+        deserialize_into(deserializer, &mut bytes)?;
+        Self::from_compact(&bytes).map_err(de::Error::custom)
+    }
+}
+
+/// Serde functions for the secret key, which should be opted in by `#[serde(with = "...")]`.
+///
+/// The secret key is not serializable by default, to avoid leaking it by accident.
+pub mod secret_key {
+    use serde::{de, Deserializer, Serializer};
+
+    use crate::secp256k1::SecretKey;
+
+    pub fn serialize<S: Serializer>(sk: &SecretKey, serializer: S) -> Result<S::Ok, S::Error> {
+        super::serialize_hex(&sk[..], serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<SecretKey, D::Error> {
+        let bytes = super::deserialize_hex(deserializer)?;
+        SecretKey::from_slice(&bytes).map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use slices::u8_slice;
+use std::str::FromStr;
+
+use crate::{
+    address::{Address, Args, CodeHash, CodeHashIndex, CodeHashType, Network, Since},
+    secp256k1::{self, PublicKey, SecretKey, Signature},
+    PubKeyHash,
+};
+
+fn round_trip<T>(value: &T, expected: Value) -> T
+where
+    T: Serialize + for<'de> Deserialize<'de>,
+{
+    let actual = serde_json::to_value(value).unwrap();
+    assert_eq!(actual, expected);
+    serde_json::from_value(actual).unwrap()
+}
+
+#[test]
+fn test_address_types() {
+    let s = "ckb1qyqt8xaupvm8837nv3gtc9x0ekkj64vud3jqfwyw5v";
+    let address = Address::from_str(s).unwrap();
+    assert_eq!(round_trip(&address, json!(s)).to_string(), s);
+    assert_eq!(round_trip(&Network::Test, json!("ckt")), Network::Test);
+    let index = CodeHash::Index(CodeHashIndex::Secp256k1MultiSig);
+    assert_eq!(
+        round_trip(&index, json!({ "index": "secp256k1_multisig" })),
+        index
+    );
+    let code_hash = "0x9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8";
+    let data = CodeHash::Data {
+        hash_type: CodeHashType::Data1,
+        content: *u8_slice!("0x9bd7e06f3ecf4be0f2fcd2188b23f1b9fcc88e5d4b65a8637b17723bbda3cce8"),
+    };
+    assert_eq!(
+        round_trip(
+            &data,
+            json!({ "code_hash": code_hash, "hash_type": "data1" })
+        ),
+        data
+    );
+    let args = address.args();
+    let json = json!("0xb39bbc0b3673c7d36450bc14cfcdad2d559c6c64");
+    assert_eq!(&round_trip(args, json), args);
+    let script = address.to_script();
+    let json = json!({
+        "code_hash": code_hash,
+        "hash_type": "type",
+        "args": "0xb39bbc0b3673c7d36450bc14cfcdad2d559c6c64",
+    });
+    assert_eq!(round_trip(&script, json), script);
+    let since = Since::block_number(true, 100).unwrap();
+    assert_eq!(round_trip(&since, json!("0x8000000000000064")), since);
+
+    for (invalid, json) in &[
+        ("address", json!("ckb1invalid")),
+        ("network", json!("unknown")),
+        ("args", json!("b39b")),
+        ("args", json!("0xb39")),
+        ("since", json!("0x6000000000000000")),
+        (
+            "code hash",
+            json!({ "code_hash": "0x00", "hash_type": "type" }),
+        ),
+        (
+            "code hash",
+            json!({ "index": "secp256k1_multisig", "hash_type": "type" }),
+        ),
+    ] {
+        let result = match *invalid {
+            "address" => serde_json::from_value::<Address>(json.clone()).map(|_| ()),
+            "network" => serde_json::from_value::<Network>(json.clone()).map(|_| ()),
+            "args" => serde_json::from_value::<Args>(json.clone()).map(|_| ()),
+            "since" => serde_json::from_value::<Since>(json.clone()).map(|_| ()),
+            _ => serde_json::from_value::<CodeHash>(json.clone()).map(|_| ()),
+        };
+        assert!(result.is_err(), "{} should be invalid: {}", invalid, json);
+    }
+}
+
+#[test]
+fn test_key_types() {
+    let sk = SecretKey::from_slice(u8_slice!(
+        "0x0000000000000000000000000000000000000000000000000000000000000001"
+    ))
+    .unwrap();
+    let pk = sk.public_key();
+    let json = json!("0x0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+    assert_eq!(round_trip(&pk, json), pk);
+    let pkhash = pk.pkhash_blake160();
+    let json = json!(format!("0x{}", pkhash));
+    assert_eq!(round_trip(&pkhash, json).as_slice(), pkhash.as_slice());
+    let _: PubKeyHash = serde_json::from_value(json!(format!("0x{}", pkhash))).unwrap();
+    let signature = sk.sign_recoverable(&[0u8; 32]).unwrap();
+    let json = json!(format!("0x{}", signature));
+    assert_eq!(round_trip(&signature, json), signature);
+    assert!(serde_json::from_value::<Signature>(json!("0x00")).is_err());
+    let standard = signature.to_standard();
+    let json = json!(format!("0x{}", standard));
+    assert_eq!(round_trip(&standard, json), standard);
+    assert!(serde_json::from_value::<PublicKey>(json!("0x00")).is_err());
+
+    #[derive(Serialize, Deserialize)]
+    struct Wallet {
+        #[serde(with = "secp256k1::serde_secret_key")]
+        secret_key: SecretKey,
+    }
+    let wallet = Wallet { secret_key: sk };
+    let json = json!({
+        "secret_key": "0x0000000000000000000000000000000000000000000000000000000000000001"
+    });
+    assert_eq!(serde_json::to_value(&wallet).unwrap(), json);
+    let wallet: Wallet = serde_json::from_value(json).unwrap();
+    assert_eq!(wallet.secret_key.public_key(), pk);
+}
@@ -0,0 +1,23 @@
+// Copyright (C) 2019-2020 Boyu Yang
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use ripemd160::Ripemd160;
+use sha2::{Digest, Sha256};
+
+pub fn sha256<T: AsRef<[u8]>>(s: T) -> [u8; 32] {
+    let mut result = [0u8; 32];
+    result.copy_from_slice(&Sha256::digest(s.as_ref())[..]);
+    result
+}
+
+/// The ripemd160 hash of the sha256 hash, as the Bitcoin public key hash.
+pub fn hash160<T: AsRef<[u8]>>(s: T) -> [u8; 20] {
+    let mut result = [0u8; 20];
+    result.copy_from_slice(&Ripemd160::digest(&sha256(s)[..])[..]);
+    result
+}
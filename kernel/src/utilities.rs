@@ -53,6 +53,17 @@ macro_rules! impl_std_fmt_masked {
     };
 }
 
+#[cfg(test)]
+#[allow(unused_macros)]
+macro_rules! assert_matches {
+    ($expr:expr, $pat:pat $(if $guard:expr)?) => {
+        match $expr {
+            $pat $(if $guard)? => {}
+            _ => panic!("assertion failed: does not match `{}`", stringify!($pat)),
+        }
+    };
+}
+
 pub fn hex_string(bin: &[u8]) -> String {
     faster_hex::hex_string(bin).unwrap()
 }
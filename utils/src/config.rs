@@ -10,7 +10,7 @@ use std::convert::TryFrom;
 
 use property::Property;
 
-use kernel::{address, secp256k1, HashAlgo, SignAlgo};
+use kernel::{address, bip32, bip39, secp256k1, HashAlgo, SignAlgo};
 
 use crate::error::{Error, Result};
 
@@ -25,6 +25,13 @@ pub(crate) enum AppConfig {
 pub(crate) struct KeyArgs {
     sign_algo: SignAlgo,
     hash_algo: HashAlgo,
+    hd: Option<HdArgs>,
+}
+
+#[derive(Property)]
+pub(crate) struct HdArgs {
+    mnemonic: bip39::Mnemonic,
+    path: bip32::DerivationPath,
 }
 
 #[derive(Property)]
@@ -48,7 +55,7 @@ pub(crate) fn build_commandline() -> Result<AppConfig> {
     let yaml = clap::load_yaml!("cli.yaml");
     let matches = clap::App::from_yaml(yaml)
         .version(clap::crate_version!())
-        .author(clap::crate_authors!("\n"))
+        .author(env!("CARGO_PKG_AUTHORS"))
         .get_matches();
     AppConfig::try_from(&matches)
 }
@@ -69,10 +76,42 @@ impl<'a> TryFrom<&'a clap::ArgMatches<'a>> for AppConfig {
 impl<'a> TryFrom<&'a clap::ArgMatches<'a>> for KeyArgs {
     type Error = Error;
     fn try_from(matches: &'a clap::ArgMatches) -> Result<Self> {
-        let secret_opt = matches
-            .value_of("secret")
-            .map(|hex_str| decode_hex(&hex_str))
+        let mnemonic_opt = if let Some(phrase) = matches.value_of("mnemonic") {
+            Some(bip39::Mnemonic::from_phrase(phrase)?)
+        } else if let Some(words) = matches.value_of("words") {
+            let words = words
+                .parse::<usize>()
+                .map_err(|err| Error::Args(err.to_string()))?;
+            Some(bip39::Mnemonic::random(words / 3 * 32)?)
+        } else {
+            None
+        };
+        let hd = mnemonic_opt
+            .map(|mnemonic| -> Result<HdArgs> {
+                let index = matches
+                    .value_of("index")
+                    .map(|value| {
+                        value
+                            .parse::<u32>()
+                            .map_err(|err| Error::Args(err.to_string()))
+                    })
+                    .transpose()?
+                    .unwrap_or_else(|| unreachable!());
+                let path = bip32::DerivationPath::ckb_account(0)?
+                    .child(bip32::ChildNumber::normal(
+                        bip32::KeyChain::External as u32,
+                    )?)
+                    .child(bip32::ChildNumber::normal(index)?);
+                Ok(HdArgs { mnemonic, path })
+            })
             .transpose()?;
+        let secret_opt = if let Some(ref hd) = hd {
+            let passphrase = matches.value_of("passphrase").unwrap_or("");
+            let master = hd.mnemonic.to_master_key(passphrase)?;
+            Some(master.derive_path(&hd.path)?.secret_key()[..].to_owned())
+        } else {
+            matches.value_of("secret").map(decode_hex).transpose()?
+        };
         let sign_algo = matches
             .value_of("sign-algo")
             .map(|value| match value {
@@ -97,6 +136,7 @@ impl<'a> TryFrom<&'a clap::ArgMatches<'a>> for KeyArgs {
         Ok(Self {
             sign_algo,
             hash_algo,
+            hd,
         })
     }
 }
@@ -112,9 +152,16 @@ impl<'a> TryFrom<&'a clap::ArgMatches<'a>> for AddrArgs {
                 _ => unreachable!(),
             })
             .unwrap_or_else(|| unreachable!());
+        let format = matches.value_of("address-format").map(|value| match value {
+            "short" => address::AddressFormat::Short,
+            "full" => address::AddressFormat::Full,
+            "deprecated-full" => address::AddressFormat::DeprecatedFull,
+            _ => unreachable!(),
+        });
         let code_hash = if let Some(value) = matches.value_of("code-hash-index") {
             let index = match value {
                 "secp256k1-blake160" => address::CodeHashIndex::Secp256k1Blake160,
+                "anyone-can-pay" => address::CodeHashIndex::AnyoneCanPay,
                 _ => unreachable!(),
             };
             address::CodeHash::Index(index)
@@ -124,12 +171,14 @@ impl<'a> TryFrom<&'a clap::ArgMatches<'a>> for AddrArgs {
                 .map(|value| match value {
                     "data" => address::CodeHashType::Data,
                     "type" => address::CodeHashType::Type,
+                    "data1" => address::CodeHashType::Data1,
+                    "data2" => address::CodeHashType::Data2,
                     _ => unreachable!(),
                 })
                 .unwrap_or_else(|| unreachable!());
             matches
                 .value_of("code-hash")
-                .map(|data| decode_hex(data))
+                .map(decode_hex)
                 .transpose()?
                 .map(|data| {
                     if data.len() != 32 {
@@ -158,6 +207,7 @@ impl<'a> TryFrom<&'a clap::ArgMatches<'a>> for AddrArgs {
             .unwrap_or_else(|| unreachable!());
         let address = address::AddressBuilder::new()
             .network(network)
+            .format(format)
             .code_hash(code_hash)
             .args(address::Args::Simple(args))
             .build()?;
@@ -177,7 +227,7 @@ impl<'a> TryFrom<&'a clap::ArgMatches<'a>> for HashArgs {
             .unwrap_or_else(|| unreachable!());
         let input = matches
             .value_of("hash-input")
-            .map(|hex_str| decode_hex(&hex_str))
+            .map(decode_hex)
             .transpose()?
             .unwrap_or_else(|| unreachable!());
         Ok(Self { algo, input })
@@ -189,7 +239,7 @@ impl<'a> TryFrom<&'a clap::ArgMatches<'a>> for SignArgs {
     fn try_from(matches: &'a clap::ArgMatches) -> Result<Self> {
         let secret = matches
             .value_of("secret")
-            .map(|hex_str| decode_hex(&hex_str))
+            .map(decode_hex)
             .transpose()?
             .unwrap_or_else(|| unreachable!());
         let algo = matches
@@ -204,7 +254,7 @@ impl<'a> TryFrom<&'a clap::ArgMatches<'a>> for SignArgs {
             .unwrap_or_else(|| unreachable!());
         let input = matches
             .value_of("sign-input")
-            .map(|hex_str| decode_hex(&hex_str))
+            .map(decode_hex)
             .transpose()?
             .unwrap_or_else(|| unreachable!());
         Ok(Self { algo, input })
@@ -213,7 +263,7 @@ impl<'a> TryFrom<&'a clap::ArgMatches<'a>> for SignArgs {
 
 fn decode_hex(hex_str: &str) -> Result<Vec<u8>> {
     let hex_bytes = hex_str.as_bytes();
-    if hex_str.len() % 2 != 0 || hex_bytes.len() % 2 != 0 {
+    if hex_bytes.len() & 1 != 0 {
         return Err(Error::Hex("the format of input is not right".to_owned()));
     }
     let mut decoded = vec![0; hex_bytes.len() >> 1];
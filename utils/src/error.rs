@@ -8,7 +8,7 @@
 
 use thiserror::Error;
 
-use kernel::{address, secp256k1};
+use kernel::{address, bip32, bip39, secp256k1};
 
 #[derive(Debug, Error)]
 pub(crate) enum Error {
@@ -20,6 +20,10 @@ pub(crate) enum Error {
     Secp256k1(#[from] secp256k1::Error),
     #[error("address error: {0}")]
     Address(#[from] address::error::Error),
+    #[error("bip32 error: {0}")]
+    Bip32(#[from] bip32::error::Error),
+    #[error("bip39 error: {0}")]
+    Bip39(#[from] bip39::error::Error),
 }
 
 pub(crate) type Result<T> = ::std::result::Result<T, Error>;
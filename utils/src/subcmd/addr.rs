@@ -9,6 +9,10 @@
 use crate::{config::AddrArgs, error::Result};
 
 pub(crate) fn execute(args: AddrArgs) -> Result<()> {
-    println!("address = {}", args.address());
+    let address = args.address();
+    println!("address = {}", address);
+    if let Some(script) = address.known_script() {
+        println!("script  = {}", script.name());
+    }
     Ok(())
 }
@@ -6,7 +6,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use kernel::{HashAlgo, SignAlgo};
+use kernel::{address::Network, HashAlgo, SignAlgo};
 
 use crate::{config::KeyArgs, error::Result};
 
@@ -17,21 +17,26 @@ pub(crate) fn execute(args: KeyArgs) -> Result<()> {
             match args.hash_algo() {
                 HashAlgo::Blake2b256 => {
                     let pkh = pk.pkhash_blake160();
-                    let addrm = pkh.address(true);
-                    let addrt = pkh.address(false);
+                    let addrm = pkh.address(Network::Main)?;
+                    let addrt = pkh.address(Network::Test)?;
                     println!("Secp256k1 + Blake160:\n");
-                    println!("    secret  = {}", sk);
-                    println!("    public  = {}", pk);
-                    println!("    pk-hash = {}", pkh);
-                    println!("    mainnet = {}", addrm);
-                    println!("    testnet = {}", addrt);
+                    if let Some(hd) = args.hd() {
+                        println!("    mnemonic = {}", hd.mnemonic().phrase());
+                        println!("    path     = {}", hd.path());
+                        println!();
+                    }
+                    println!("    secret   = {}", sk);
+                    println!("    public   = {}", pk);
+                    println!("    pk-hash  = {}", pkh);
+                    println!("    mainnet  = {}", addrm);
+                    println!("    testnet  = {}", addrt);
                 }
             }
         }
     };
     println!(
         "\nNOTICE:\n\n    \
-         This utility is very simple, it just prints the secret key to the screen.\n\n    \
+         This utility is very simple, it just prints the secret key (and the mnemonic) to the screen.\n\n    \
          This brings a potential security risk:\n\n        \
          *** This secret key perhaps has been LEAKED ***\n\n        \
          (for example, someone saw it, or there is a camera behind you)\n\n    \